@@ -0,0 +1,71 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[cfg(feature = "aho-corasick")]
+use day01::get_calibration_number_aho_corasick;
+use day01::{get_calibration_number_scan, get_calibration_number_spelled_out};
+
+fn bench_lines(c: &mut Criterion, name: &str, lines: &[&str]) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("find/rfind", |b| {
+        b.iter(|| {
+            for line in lines {
+                get_calibration_number_spelled_out(black_box(line)).unwrap();
+            }
+        })
+    });
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            for line in lines {
+                get_calibration_number_scan(black_box(line)).unwrap();
+            }
+        })
+    });
+    #[cfg(feature = "aho-corasick")]
+    group.bench_function("aho-corasick", |b| {
+        b.iter(|| {
+            for line in lines {
+                get_calibration_number_aho_corasick(black_box(line)).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn spelled_out(c: &mut Criterion) {
+    let lines = [
+        "two1nine",
+        "eightwothree",
+        "abcone2threexyz",
+        "xtwone3four",
+        "4nineeightseven2",
+        "zoneight234",
+        "7pqrstsixteen",
+    ];
+    bench_lines(c, "spelled_out", &lines);
+}
+
+fn spelled_out_overlapping(c: &mut Criterion) {
+    // Long lines made up of words that overlap each other, padded so that the digits
+    // are far from both ends
+    let overlapping = [
+        "oneight",
+        "twone",
+        "threeight",
+        "fiveight",
+        "sevenine",
+        "eightwo",
+    ];
+    let lines = (0..1000)
+        .map(|line| {
+            let words = (0..50)
+                .map(|word| overlapping[(line + word) % overlapping.len()])
+                .collect::<String>();
+            format!("xyz{}xyz", words)
+        })
+        .collect::<Vec<_>>();
+    let lines = lines.iter().map(String::as_str).collect::<Vec<_>>();
+    bench_lines(c, "spelled_out_overlapping", &lines);
+}
+
+criterion_group!(benches, spelled_out, spelled_out_overlapping);
+criterion_main!(benches);
@@ -0,0 +1,336 @@
+use anyhow::{anyhow, bail};
+use itertools::Itertools;
+
+use util::{Input, Result};
+
+/// Sum the spelled out calibration numbers of all lines
+///
+/// Each line is handled separately, so a word split across two lines is never matched
+pub fn total_spelled_out(input: &Input) -> Result<u32> {
+    #[cfg(feature = "aho-corasick")]
+    let get_calibration_number = get_calibration_number_aho_corasick;
+    #[cfg(not(feature = "aho-corasick"))]
+    let get_calibration_number = get_calibration_number_spelled_out;
+
+    input
+        .trim_trailing_newlines()
+        .as_lines()
+        .map(get_calibration_number)
+        .map_ok(|nbr| nbr as u32)
+        .sum()
+}
+
+pub fn get_calibration_number(input: &str) -> Result<u8> {
+    let digits: String = input.chars().filter(|c| c.is_ascii_digit()).collect();
+    let input = match digits.len() {
+        0 => bail!("Erroneous input"),
+        1 => format!("{}{}", digits, digits),
+        2 => digits,
+        len => format!(
+            "{}{}",
+            digits
+                .chars()
+                .next()
+                .expect("Iterator has length > 2 according to match"),
+            digits
+                .chars()
+                .nth(len - 1)
+                .expect("Iterator has length > 2 according to match"),
+        ),
+    };
+    Ok(input.parse()?)
+}
+
+/// The zero digits of the Unicode decimal digit blocks that are recognised besides ASCII
+static UNICODE_ZEROS: [char; 6] = ['０', '٠', '۰', '०', '০', '๐'];
+
+/// Get the value of a decimal digit in any of the recognised scripts
+fn unicode_digit(c: char) -> Option<u8> {
+    if !c.is_numeric() {
+        return None;
+    }
+    c.to_digit(10)
+        .or_else(|| {
+            UNICODE_ZEROS
+                .iter()
+                .find_map(|zero| (c as u32).checked_sub(*zero as u32).filter(|d| *d < 10))
+        })
+        .map(|digit| digit as u8)
+}
+
+/// Get the calibration number of a single line, also recognising full-width, Arabic-Indic,
+/// Extended Arabic-Indic, Devanagari, Bengali and Thai digits
+///
+/// Decimal digits from any other script, such as Gujarati or mathematical bold digits,
+/// are ignored
+///
+/// This is slower than [get_calibration_number], which only looks for ASCII digits
+pub fn get_calibration_number_unicode(input: &str) -> Result<u8> {
+    let (first, last) = input
+        .chars()
+        .filter_map(unicode_digit)
+        .enumerate()
+        .minmax()
+        .into_option()
+        .ok_or_else(|| anyhow!("Erroneous input"))?;
+    Ok(10 * first.1 + last.1)
+}
+
+static NUMBERS: [(&str, u8); 20] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// Get the calibration number of a single line, where digits may be spelled out
+pub fn get_calibration_number_spelled_out(input: &str) -> Result<u8> {
+    debug_assert!(!input.contains('\n'), "Calibration numbers are per line");
+    // Find the first and last occurrences of each digit or word
+    // and sort them according to where in the string they occur
+    let digits = NUMBERS
+        .iter()
+        .flat_map(|(word, value)| {
+            [
+                input.find(word).map(|position| (position, *value)),
+                input.rfind(word).map(|position| (position, *value)),
+            ]
+        })
+        .flatten()
+        .sorted()
+        .collect_vec();
+
+    // Get the first and last digits
+    let first = digits.first();
+    let last = digits.last();
+    match (first, last) {
+        // These are either always Some, Some or None, None
+        (Some(first), Some(last)) => Ok(10 * first.1 + last.1),
+        _ => Err(anyhow!("Invalid input")),
+    }
+}
+
+/// Get the calibration number of a single line, where digits may be spelled out,
+/// scanning inwards from each end of the line and stopping at the first digit or word found
+pub fn get_calibration_number_scan(input: &str) -> Result<u8> {
+    let digit_at = |position: usize| {
+        NUMBERS
+            .iter()
+            .find(|(word, _)| input[position..].starts_with(word))
+            .map(|(_, value)| *value)
+    };
+    let positions = input.char_indices().map(|(position, _)| position);
+    let first = positions
+        .clone()
+        .find_map(digit_at)
+        .ok_or_else(|| anyhow!("Invalid input"))?;
+    let last = positions
+        .rev()
+        .find_map(digit_at)
+        .expect("There is a last digit when there is a first");
+    Ok(10 * first + last)
+}
+
+/// Get the calibration number of a single line, where digits may be spelled out,
+/// finding all words and digits in a single pass with an Aho-Corasick automaton
+#[cfg(feature = "aho-corasick")]
+pub fn get_calibration_number_aho_corasick(input: &str) -> Result<u8> {
+    use aho_corasick::AhoCorasick;
+    use std::sync::OnceLock;
+
+    // Build the automaton once and reuse it for every line
+    static AUTOMATON: OnceLock<AhoCorasick> = OnceLock::new();
+    let automaton = AUTOMATON.get_or_init(|| {
+        AhoCorasick::new(NUMBERS.iter().map(|(word, _)| word)).expect("Numbers are valid patterns")
+    });
+
+    // Overlapping matches are needed so that e.g. "twone" ends with a one
+    let (first, last) = automaton
+        .find_overlapping_iter(input)
+        .map(|found| (found.start(), NUMBERS[found.pattern().as_usize()].1))
+        .minmax()
+        .into_option()
+        .ok_or_else(|| anyhow!("Invalid input"))?;
+    Ok(10 * first.1 + last.1)
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "aho-corasick")]
+    use crate::get_calibration_number_aho_corasick;
+    use crate::{
+        get_calibration_number, get_calibration_number_scan, get_calibration_number_spelled_out,
+        get_calibration_number_unicode, total_spelled_out,
+    };
+    use rstest::rstest;
+    use util::Input;
+
+    #[rstest]
+    #[case("12", 12)]
+    #[case("11", 11)]
+    #[case("1", 11)]
+    #[case("1abc2", 12)]
+    #[case("pqr3stu8vwx", 38)]
+    #[case("a1b2c3d4e5f", 15)]
+    #[case("treb7uchet", 77)]
+    pub fn that_get_calibration_number_returns_correct_calibration_number(
+        #[case] input: &str,
+        #[case] expected: u8,
+    ) {
+        // When the calibration number is extracted
+        let nbr = get_calibration_number(input);
+
+        // Then it is as expected
+        assert_eq!(expected, nbr.unwrap());
+    }
+
+    #[rstest]
+    #[case("1abc2", 12)]
+    #[case("１abc２", 12)]
+    #[case("pqr３stu8vwx", 38)]
+    #[case("ａ１ｂ２ｃ３", 13)]
+    #[case("treb٧uchet", 77)]
+    #[case("४two२", 42)]
+    #[case("1૧𝟏2", 12)]
+    pub fn that_get_calibration_number_unicode_returns_correct_calibration_number(
+        #[case] input: &str,
+        #[case] expected: u8,
+    ) {
+        // When the calibration number is extracted
+        let nbr = get_calibration_number_unicode(input);
+
+        // Then it is as expected
+        assert_eq!(expected, nbr.unwrap());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("½Ⅻ")]
+    #[case("૧𝟏")]
+    pub fn that_get_calibration_number_unicode_for_erroneous_input_returns_err(
+        #[case] input: &str,
+    ) {
+        // When the calibration number is extracted from input without decimal digits
+        let result = get_calibration_number_unicode(input);
+
+        // Then it is an error
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case("12", 12)]
+    #[case("11", 11)]
+    #[case("1", 11)]
+    #[case("1abc2", 12)]
+    #[case("pqr3stu8vwx", 38)]
+    #[case("a1b2c3d4e5f", 15)]
+    #[case("treb7uchet", 77)]
+    #[case("two1nine", 29)]
+    #[case("eightwothree", 83)]
+    #[case("abcone2threexyz", 13)]
+    #[case("xtwone3four", 24)]
+    #[case("4nineeightseven2", 42)]
+    #[case("zoneight234", 14)]
+    #[case("7pqrstsixteen", 76)]
+    #[case("7pqrsteighthree", 73)]
+    #[case("7237", 77)]
+    pub fn that_get_calibration_number_spelled_out_returns_correct_calibration_number(
+        #[case] input: &str,
+        #[case] expected: u8,
+    ) {
+        // When the calibration number is extracted
+        let nbr = get_calibration_number_spelled_out(input);
+
+        // Then it is as expected
+        assert_eq!(expected, nbr.unwrap());
+    }
+
+    #[rstest]
+    #[case("12")]
+    #[case("1")]
+    #[case("two1nine")]
+    #[case("eightwothree")]
+    #[case("xtwone3four")]
+    #[case("zoneight234")]
+    #[case("7pqrsteighthree")]
+    #[case("oneightwoneight")]
+    #[case("å1ønine")]
+    #[case("")]
+    #[case("abcdef")]
+    pub fn that_get_calibration_number_scan_equals_naive(#[case] input: &str) {
+        // When the calibration number is extracted with both implementations
+        let naive = get_calibration_number_spelled_out(input);
+        let scan = get_calibration_number_scan(input);
+
+        // Then they agree
+        assert_eq!(naive.ok(), scan.ok());
+    }
+
+    #[cfg(feature = "aho-corasick")]
+    #[rstest]
+    #[case("12")]
+    #[case("11")]
+    #[case("1")]
+    #[case("1abc2")]
+    #[case("pqr3stu8vwx")]
+    #[case("a1b2c3d4e5f")]
+    #[case("treb7uchet")]
+    #[case("two1nine")]
+    #[case("eightwothree")]
+    #[case("abcone2threexyz")]
+    #[case("xtwone3four")]
+    #[case("4nineeightseven2")]
+    #[case("zoneight234")]
+    #[case("7pqrstsixteen")]
+    #[case("7pqrsteighthree")]
+    #[case("7237")]
+    #[case("")]
+    #[case("abcdef")]
+    pub fn that_get_calibration_number_aho_corasick_equals_naive(#[case] input: &str) {
+        // When the calibration number is extracted with both implementations
+        let naive = get_calibration_number_spelled_out(input);
+        let aho_corasick = get_calibration_number_aho_corasick(input);
+
+        // Then they agree
+        assert_eq!(naive.ok(), aho_corasick.ok());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("abcdef")]
+    pub fn that_get_calibration_number_for_erroneous_input_returns_err(#[case] input: &str) {
+        // When the calibration number is extracted
+        let result = get_calibration_number(input);
+
+        // Then it is an error
+        assert!(result.is_err());
+    }
+
+    #[test]
+    pub fn test_total_spelled_out_does_not_match_across_lines() {
+        // Given a word split across two lines
+        let input = Input::from_lines(["1tw", "o3"]);
+
+        // When the total is calculated
+        let total = total_spelled_out(&input);
+
+        // Then each line only sees its own digits
+        assert_eq!(total.unwrap(), 11 + 33);
+    }
+}
@@ -0,0 +1,808 @@
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use std::str::FromStr;
+
+use util::{Input, Result};
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Game {
+    id: u32,
+    hands: Vec<Hand>,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Color {
+    pub const ALL: [Color; 3] = [Color::Red, Color::Green, Color::Blue];
+}
+
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "red" => Ok(Color::Red),
+            "green" => Ok(Color::Green),
+            "blue" => Ok(Color::Blue),
+            other => Err(anyhow!("Illegal colour {}", other)),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Hand {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl Hand {
+    pub fn new(red: u32, green: u32, blue: u32) -> Hand {
+        Hand { red, green, blue }
+    }
+
+    /// Parse a hand, also accepting cubes joined with "and", with or without an Oxford comma
+    ///
+    /// The counts of a colour that is listed more than once are summed
+    pub fn parse(input: &str) -> Result<Hand> {
+        let cubes = input.split(',').flat_map(|cube| {
+            let cube = cube.trim();
+            cube.strip_prefix("and ").unwrap_or(cube).split(" and ")
+        });
+        Hand::parse_cubes(cubes)
+    }
+
+    /// Parse a hand where cubes are only separated by commas
+    pub fn parse_strict(input: &str) -> Result<Hand> {
+        Hand::parse_cubes(input.split(','))
+    }
+
+    fn parse_cubes<'a>(cubes: impl Iterator<Item = &'a str>) -> Result<Hand> {
+        let hand = cubes
+            .map(|cube| {
+                let (count, colour) = cube
+                    .trim()
+                    .splitn(2, ' ')
+                    .map(str::trim)
+                    .collect_tuple()
+                    .ok_or_else(|| anyhow!("Invalid cube declaration {}", cube))?;
+                let count: u32 = count.parse()?;
+                Ok((colour.parse::<Color>()?, count))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            // A colour listed more than once in the same hand counts all of its cubes
+            .try_fold(
+                Hand::default(),
+                |mut hand, (colour, count)| -> Result<Hand> {
+                    let total = hand
+                        .get(colour)
+                        .checked_add(count)
+                        .ok_or_else(|| anyhow!("Too many {:?} cubes in one hand", colour))?;
+                    hand.set(colour, total);
+                    Ok(hand)
+                },
+            )?;
+        Ok(hand)
+    }
+
+    /// Get the number of cubes of a colour
+    pub fn get(&self, colour: Color) -> u32 {
+        match colour {
+            Color::Red => self.red,
+            Color::Green => self.green,
+            Color::Blue => self.blue,
+        }
+    }
+
+    /// Set the number of cubes of a colour
+    pub fn set(&mut self, colour: Color, count: u32) {
+        match colour {
+            Color::Red => self.red = count,
+            Color::Green => self.green = count,
+            Color::Blue => self.blue = count,
+        }
+    }
+
+    pub fn is_valid(&self, limits: &Hand) -> bool {
+        limits.covers(self)
+    }
+
+    /// Check if this hand has at least as many cubes of every colour as another hand
+    pub fn covers(&self, other: &Hand) -> bool {
+        self.red >= other.red && self.green >= other.green && self.blue >= other.blue
+    }
+
+    pub fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+
+    /// Cap each colour at the number of cubes of that colour in the limits
+    pub fn clamp_to(&self, limits: &Hand) -> Hand {
+        Hand {
+            red: self.red.min(limits.red),
+            green: self.green.min(limits.green),
+            blue: self.blue.min(limits.blue),
+        }
+    }
+}
+
+/// Scale each colour of a hand, as if the same draw was repeated
+///
+/// Overflows like any u32 multiplication, i.e. panics in debug builds and wraps in release builds
+impl std::ops::Mul<u32> for Hand {
+    type Output = Hand;
+
+    fn mul(self, factor: u32) -> Hand {
+        Hand {
+            red: self.red * factor,
+            green: self.green * factor,
+            blue: self.blue * factor,
+        }
+    }
+}
+
+impl Game {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn parse_many(input: &Input) -> Result<Vec<Game>> {
+        input
+            .trim_trailing_newlines()
+            .as_lines()
+            .map(Game::parse)
+            .collect::<Result<Vec<_>>>()
+    }
+
+    /// Parse all games while summing the ids of the valid games and the total power,
+    /// failing on the first line that can't be parsed
+    pub fn parse_validated_many(input: &Input, limits: &Hand) -> Result<(u32, u32)> {
+        input
+            .trim_trailing_newlines()
+            .as_lines()
+            .enumerate()
+            .try_fold((0, 0), |(id_sum, total_power), (index, line)| {
+                let game = Game::parse(line)
+                    .with_context(|| format!("Invalid game on line {}: {}", index + 1, line))?;
+                let id = if game.is_valid(limits) { game.id } else { 0 };
+                Ok((id_sum + id, total_power + game.power()))
+            })
+    }
+
+    pub fn parse(input: &str) -> Result<Game> {
+        let (declaration, cubes) = input
+            .splitn(2, ':')
+            .collect_tuple()
+            .ok_or_else(|| anyhow!("Invalid game {}", input))?;
+        let (_, id) = declaration
+            .split_whitespace()
+            .collect_tuple()
+            .ok_or_else(|| anyhow!("Invalid game ID {}", declaration))?;
+        let id = id.parse()?;
+        // A game without any cubes at all has shown a single empty hand
+        if cubes.trim().is_empty() {
+            return Ok(Game {
+                id,
+                hands: vec![Hand::default()],
+            });
+        }
+        let hands = cubes
+            .split(';')
+            .map(Hand::parse)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Game { id, hands })
+    }
+
+    pub fn is_valid(&self, limits: &Hand) -> bool {
+        self.hands.iter().all(|hand| hand.is_valid(limits))
+    }
+
+    pub fn power(&self) -> u32 {
+        self.max_per_color().power()
+    }
+
+    /// Check if every hand could have been drawn from a bag with the given cubes,
+    /// which is the same as the game being valid with the bag as limits
+    pub fn is_reachable_from_bag(&self, bag: &Hand) -> bool {
+        self.is_valid(bag)
+    }
+
+    /// Get the cubes left in the bag while a hand is drawn from it,
+    /// or None if there is no such hand or it can't be drawn from the bag
+    pub fn remaining_after(&self, hand_index: usize, bag: &Hand) -> Option<Hand> {
+        let hand = self.hands.get(hand_index)?;
+        Some(Hand {
+            red: bag.red.checked_sub(hand.red)?,
+            green: bag.green.checked_sub(hand.green)?,
+            blue: bag.blue.checked_sub(hand.blue)?,
+        })
+    }
+
+    /// Get the number of hands shown in the game
+    pub fn hand_count(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// Check if two games have the same id and the same hands, regardless of the order of the hands
+    pub fn equivalent(&self, other: &Game) -> bool {
+        let sorted = |game: &Game| {
+            game.hands
+                .iter()
+                .map(|hand| (hand.red, hand.green, hand.blue))
+                .sorted()
+                .collect_vec()
+        };
+        self.id == other.id && sorted(self) == sorted(other)
+    }
+
+    /// Iterate over the hands of the game together with their index
+    pub fn enumerate_hands(&self) -> impl Iterator<Item = (usize, &Hand)> {
+        self.hands.iter().enumerate()
+    }
+
+    /// List every colour in every hand that exceeds the limits as (hand index, colour, seen, limit)
+    pub fn violations(&self, limits: &Hand) -> Vec<(usize, Color, u32, u32)> {
+        self.enumerate_hands()
+            .flat_map(|(index, hand)| {
+                Color::ALL
+                    .into_iter()
+                    .filter(|colour| hand.get(*colour) > limits.get(*colour))
+                    .map(move |colour| (index, colour, hand.get(colour), limits.get(colour)))
+            })
+            .collect()
+    }
+
+    /// Find the colour that comes closest to, or furthest exceeds, its limit across all hands
+    ///
+    /// Only colours that reach at least half of their limit are considered, so a game that is
+    /// trivially far from all limits (including one without any cubes) gives None
+    pub fn tightest_color(&self, limits: &Hand) -> Option<Color> {
+        let maximums = self.max_per_color();
+        Color::ALL
+            .into_iter()
+            .filter(|colour| maximums.get(*colour) > 0)
+            .filter(|colour| maximums.get(*colour) >= limits.get(*colour).div_ceil(2))
+            .min_by_key(|colour| limits.get(*colour) as i64 - maximums.get(*colour) as i64)
+    }
+
+    /// Get the highest number of cubes seen of each colour across all hands
+    pub fn max_per_color(&self) -> Hand {
+        self.hands
+            .iter()
+            .fold(Hand::default(), |maximums, hand| Hand {
+                red: maximums.red.max(hand.red),
+                green: maximums.green.max(hand.green),
+                blue: maximums.blue.max(hand.blue),
+            })
+    }
+
+    /// Render the id, validity and power of each game as CSV, ordered by game id
+    pub fn results_csv(games: &[Game], limits: &Hand) -> String {
+        games
+            .iter()
+            .sorted_by_key(|game| game.id)
+            .fold(String::from("id,valid,power\n"), |csv, game| {
+                csv + &format!("{},{},{}\n", game.id, game.is_valid(limits), game.power())
+            })
+    }
+}
+
+/// Find the smallest limits that make every game valid
+pub fn minimum_limits(games: &[Game]) -> Hand {
+    games
+        .iter()
+        .map(Game::max_per_color)
+        .fold(Hand::default(), |limits, maximums| Hand {
+            red: limits.red.max(maximums.red),
+            green: limits.green.max(maximums.green),
+            blue: limits.blue.max(maximums.blue),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{minimum_limits, Color, Game, Hand};
+    use rstest::rstest;
+    use util::{Input, Result};
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", Game {
+        id: 1,
+        hands: vec![
+            Hand::new(4, 0, 3), Hand::new(1, 2, 6), Hand::new(0, 2, 0)
+        ]
+    })]
+    #[case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", Game {
+        id: 2,
+        hands: vec![
+            Hand::new(0, 2, 1), Hand::new(1, 3, 4), Hand::new(0, 1, 1)
+        ]
+    })]
+    pub fn test_parse_game_parses_correctly(#[case] input: &str, #[case] expected: Game) {
+        // When the input is parsed
+        let parsed = Game::parse(input);
+
+        // Then the returned game is as expected
+        assert_eq!(parsed.unwrap(), expected)
+    }
+
+    #[rstest]
+    #[case("4 red, 2 green and 3 blue")]
+    #[case("4 red, 2 green, and 3 blue")]
+    #[case("4 red and 2 green and 3 blue")]
+    pub fn test_parse_hand_with_and(#[case] input: &str) {
+        // When a hand using "and" is parsed
+        let parsed = Hand::parse(input);
+
+        // Then it equals the comma separated hand
+        assert_eq!(
+            parsed.unwrap(),
+            Hand::parse("4 red, 2 green, 3 blue").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(Color::Red)]
+    #[case(Color::Green)]
+    #[case(Color::Blue)]
+    pub fn test_hand_get_set_round_trip(#[case] colour: Color) {
+        // Given a hand
+        let mut hand = Hand::new(1, 2, 3);
+
+        // When the count of a colour is set
+        hand.set(colour, 7);
+
+        // Then only that colour has changed
+        assert_eq!(hand.get(colour), 7);
+        assert_eq!(
+            hand.red + hand.green + hand.blue,
+            1 + 2 + 3 + 7 - Hand::new(1, 2, 3).get(colour)
+        );
+    }
+
+    #[rstest]
+    #[case("2 red", Color::Red)]
+    #[case("2 green", Color::Green)]
+    #[case("2 blue", Color::Blue)]
+    pub fn test_parse_hand_sets_colour(#[case] input: &str, #[case] colour: Color) {
+        // When a hand with a single colour is parsed
+        let hand = Hand::parse(input).unwrap();
+
+        // Then that colour is set
+        assert_eq!(hand.get(colour), 2);
+    }
+
+    #[test]
+    pub fn test_parse_hand_with_zero_count() {
+        // When a hand with an explicit zero count is parsed
+        let hand = Hand::parse("0 red, 2 green");
+
+        // Then that colour is zero
+        assert_eq!(hand.unwrap(), Hand::new(0, 2, 0));
+    }
+
+    #[rstest]
+    #[case("Game 1:")]
+    #[case("Game 1:  ")]
+    pub fn test_parse_game_without_cubes(#[case] input: &str) {
+        // When a game without any cubes is parsed
+        let parsed = Game::parse(input);
+
+        // Then it has a single empty hand
+        assert_eq!(
+            parsed.unwrap(),
+            Game {
+                id: 1,
+                hands: vec![Hand::default()]
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("1 red, 2 red", Hand::new(3, 0, 0))]
+    #[case("1 red, 2 green, 4 red", Hand::new(5, 2, 0))]
+    #[case("1 blue and 2 blue, and 3 blue", Hand::new(0, 0, 6))]
+    pub fn test_parse_hand_with_repeated_colour(#[case] input: &str, #[case] expected: Hand) {
+        // When a hand listing a colour more than once is parsed
+        let hand = Hand::parse(input);
+
+        // Then the counts of that colour are summed
+        assert_eq!(hand.unwrap(), expected);
+    }
+
+    #[test]
+    pub fn test_parse_hand_with_overflowing_repeated_colour() {
+        // When a hand repeating a colour with counts that don't fit together is parsed
+        let parsed = Hand::parse("4294967295 red, 1 red");
+
+        // Then it is an error
+        assert!(parsed.is_err());
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue;; 4 red")]
+    #[case("Game 1: 3 blue;")]
+    #[case("Game 1: ; 3 blue")]
+    pub fn test_parse_game_with_empty_hand(#[case] input: &str) {
+        // When a game with an empty hand among other hands is parsed
+        let parsed = Game::parse(input);
+
+        // Then it is an error
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    pub fn test_parse_hand_with_illegal_colour() {
+        // When a hand with an unknown colour is parsed
+        let parsed = Hand::parse("2 red, 3 purple");
+
+        // Then it is an error
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    pub fn test_parse_strict_hand_rejects_and() {
+        // When a hand using "and" is parsed strictly
+        let parsed = Hand::parse_strict("4 red, 2 green and 3 blue");
+
+        // Then it is an error
+        assert!(parsed.is_err());
+    }
+
+    #[rstest]
+    #[case("Game  1 :  3 blue, 4 red")]
+    #[case("Game 1:3 blue, 4 red")]
+    #[case(" Game\t1 : 3 blue , 4 red ")]
+    pub fn test_parse_game_with_irregular_spacing(#[case] input: &str) {
+        // When a game with irregular spacing is parsed
+        let parsed = Game::parse(input);
+
+        // Then it is parsed as if spaced normally
+        assert_eq!(
+            parsed.unwrap(),
+            Game {
+                id: 1,
+                hands: vec![Hand::new(4, 0, 3)]
+            }
+        )
+    }
+
+    #[rstest]
+    #[case(Game {
+            id: 1,
+            hands: vec![
+                Hand::new(4, 0, 3), Hand::new(1, 2, 6), Hand::new(0, 2, 0)
+            ]
+        },
+        4 * 2 * 6,
+    )]
+    #[case(Game {
+            id: 2,
+            hands: vec![
+                Hand::new(0, 2, 1), Hand::new(1, 3, 4), Hand::new(0, 1, 1)
+            ]
+        },
+        3 * 4,
+    )]
+    pub fn test_game_power_is_calculated_correctly(#[case] game: Game, #[case] expected: u32) {
+        // When the power of the game is retrieved
+        let power = game.power();
+
+        // Then the returned game is as expected
+        assert_eq!(power, expected)
+    }
+
+    #[rstest]
+    #[case(
+        Game {
+            id: 1,
+            hands: vec![
+                Hand::new(4, 0, 3), Hand::new(1, 2, 6), Hand::new(0, 2, 0)
+            ],
+        },
+        Hand::new(12, 13, 14),
+        true,
+    )]
+    #[case(
+        Game {
+            id: 3,
+            hands: vec![
+                Hand::new(20, 8, 6), Hand::new(4, 13, 5), Hand::new(1, 5, 0)
+            ],
+        },
+        Hand::new(12, 13, 14),
+        false,
+    )]
+    pub fn test_is_game_valid(#[case] game: Game, #[case] limits: Hand, #[case] expected: bool) {
+        // When validity of the game is checked
+        let valid = game.is_valid(&limits);
+
+        // Then the validity is as expected
+        assert_eq!(expected, valid);
+    }
+
+    #[rstest]
+    #[case(Hand::new(1, 2, 3), Hand::new(1, 2, 3), true)]
+    #[case(Hand::new(4, 5, 6), Hand::new(1, 2, 3), true)]
+    #[case(Hand::new(4, 5, 6), Hand::new(0, 0, 0), true)]
+    #[case(Hand::new(4, 5, 6), Hand::new(5, 2, 3), false)]
+    #[case(Hand::new(4, 5, 6), Hand::new(1, 6, 3), false)]
+    #[case(Hand::new(4, 5, 6), Hand::new(1, 2, 7), false)]
+    #[case(Hand::new(1, 2, 3), Hand::new(4, 5, 6), false)]
+    pub fn test_hand_covers(#[case] hand: Hand, #[case] other: Hand, #[case] expected: bool) {
+        // When it is checked if the hand covers the other hand
+        let covers = hand.covers(&other);
+
+        // Then the coverage is as expected
+        assert_eq!(covers, expected);
+    }
+
+    #[rstest]
+    #[case(Hand::new(20, 8, 15), Hand::new(12, 8, 14))]
+    #[case(Hand::new(1, 2, 3), Hand::new(1, 2, 3))]
+    #[case(Hand::new(13, 14, 15), Hand::new(12, 13, 14))]
+    pub fn test_hand_clamp_to(#[case] hand: Hand, #[case] expected: Hand) {
+        // When the hand is clamped to the limits
+        let clamped = hand.clamp_to(&Hand::new(12, 13, 14));
+
+        // Then each colour is at most its limit
+        assert_eq!(clamped, expected);
+    }
+
+    #[test]
+    pub fn test_hand_mul() {
+        // Given a hand
+        let hand = Hand::new(1, 2, 3);
+
+        // When it is multiplied by 3
+        let scaled = hand * 3;
+
+        // Then each colour is tripled
+        assert_eq!(scaled.get(Color::Red), 3);
+        assert_eq!(scaled.get(Color::Green), 6);
+        assert_eq!(scaled.get(Color::Blue), 9);
+    }
+
+    #[test]
+    pub fn test_max_per_color() {
+        // Given a game
+        let game = Game {
+            id: 1,
+            hands: vec![Hand::new(4, 0, 3), Hand::new(1, 2, 6), Hand::new(0, 2, 0)],
+        };
+
+        // Expect the maximum of each colour to be collected into a hand
+        assert_eq!(game.max_per_color(), Hand::new(4, 2, 6));
+    }
+
+    #[test]
+    pub fn test_hand_count() -> Result<()> {
+        let input = Input::from_lines([
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]);
+        let games = Game::parse_many(&input)?;
+        assert_eq!(
+            games.iter().map(Game::hand_count).collect::<Vec<_>>(),
+            vec![3, 3, 3, 3, 2]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Game 1: 2 green; 3 blue, 4 red; 1 red, 2 green, 6 blue", true)]
+    #[case("Game 1: 3 blue, 4 red; 2 green; 1 red, 2 green, 6 blue", true)]
+    #[case("Game 2: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", false)]
+    #[case(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green; 2 green",
+        false
+    )]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 3 green", false)]
+    pub fn test_equivalent(#[case] other: &str, #[case] expected: bool) -> Result<()> {
+        // Given a game and another game
+        let game = Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")?;
+        let other = Game::parse(other)?;
+
+        // Expect them to be equivalent if only the order of the hands differs
+        assert_eq!(game.equivalent(&other), expected);
+        assert_ne!(game, other);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_enumerate_hands() -> Result<()> {
+        // Given a game
+        let game = Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")?;
+
+        // When the hands are enumerated
+        let (indices, hands): (Vec<usize>, Vec<&Hand>) = game.enumerate_hands().unzip();
+
+        // Then the indices run over all hands in order
+        assert_eq!(indices, (0..game.hands.len()).collect::<Vec<_>>());
+        assert_eq!(hands[1], &Hand::new(1, 2, 6));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red")]
+    #[case("Game 4: 1 green, 11 red, 6 blue; 3 green, 6 red")]
+    pub fn test_tightest_color(#[case] input: &str) -> Result<()> {
+        // Given a game where red comes closest to the limit
+        let game = Game::parse(input)?;
+
+        // Expect red to be the tightest colour
+        assert_eq!(
+            game.tightest_color(&Hand::new(12, 13, 14)),
+            Some(Color::Red)
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Game 1: 1 red")]
+    #[case("Game 2: 5 red, 6 green; 6 blue")]
+    pub fn test_tightest_color_far_from_limits(#[case] input: &str) -> Result<()> {
+        // Given a game where every colour stays below half of its limit
+        let game = Game::parse(input)?;
+
+        // Expect there to be no tightest colour
+        assert_eq!(game.tightest_color(&Hand::new(12, 13, 14)), None);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_tightest_color_without_cubes() {
+        // Given a game without any cubes
+        let game = Game {
+            id: 1,
+            hands: vec![Hand::default()],
+        };
+
+        // Expect there to be no tightest colour
+        assert_eq!(game.tightest_color(&Hand::new(12, 13, 14)), None);
+    }
+
+    #[test]
+    pub fn test_simulate_draws_from_bag() -> Result<()> {
+        // Given a game and a bag
+        let game = Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")?;
+        let bag = Hand::new(12, 13, 14);
+
+        // When each hand is drawn from the bag in turn, putting the cubes back in between
+        let remaining = game
+            .enumerate_hands()
+            .map(|(index, _)| game.remaining_after(index, &bag))
+            .collect::<Vec<_>>();
+
+        // Then every draw leaves cubes in the bag
+        assert!(game.is_reachable_from_bag(&bag));
+        assert_eq!(
+            remaining,
+            vec![
+                Some(Hand::new(8, 13, 11)),
+                Some(Hand::new(11, 11, 8)),
+                Some(Hand::new(12, 11, 14))
+            ]
+        );
+        // And there is nothing to draw after the last hand
+        assert_eq!(game.remaining_after(3, &bag), None);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_simulate_impossible_draw() -> Result<()> {
+        // Given a game with a hand with too many red cubes for the bag
+        let game = Game::parse(
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        )?;
+        let bag = Hand::new(12, 13, 14);
+
+        // Expect the game not to be reachable and that hand not to be drawable
+        assert!(!game.is_reachable_from_bag(&bag));
+        assert_eq!(game.remaining_after(0, &bag), None);
+        assert_eq!(game.remaining_after(1, &bag), Some(Hand::new(8, 0, 9)));
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_violations() -> Result<()> {
+        // Given an impossible game
+        let game = Game::parse(
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        )?;
+
+        // When the violations are listed
+        let violations = game.violations(&Hand::new(12, 13, 14));
+
+        // Then the red count of the first hand is over the limit
+        assert_eq!(violations, vec![(0, Color::Red, 20, 12)]);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_violations_of_possible_game() -> Result<()> {
+        // Given a possible game
+        let game = Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")?;
+
+        // Expect there to be no violations
+        assert!(game.violations(&Hand::new(12, 13, 14)).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_results_csv() -> Result<()> {
+        // Given some games out of order
+        let input = Input::from_lines([
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        ]);
+        let games = Game::parse_many(&input)?;
+
+        // When the results are exported as CSV
+        let csv = Game::results_csv(&games, &Hand::new(12, 13, 14));
+
+        // Then there is a header followed by the games ordered by id
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "id,valid,power");
+        assert_eq!(lines[1], "1,true,48");
+        assert_eq!(lines.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_validated_many() -> Result<()> {
+        let input = Input::from_lines([
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]);
+        assert_eq!(
+            Game::parse_validated_many(&input, &Hand::new(12, 13, 14))?,
+            (8, 2286)
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_validated_many_reports_bad_line() {
+        // Given some input with a bad second line
+        let input = Input::from_lines([
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 purple",
+        ]);
+
+        // When the games are parsed and validated
+        let result = Game::parse_validated_many(&input, &Hand::new(12, 13, 14));
+
+        // Then the error names the line
+        assert!(result.unwrap_err().to_string().contains("line 2"));
+    }
+
+    #[test]
+    pub fn test_minimum_limits() -> Result<()> {
+        let input = Input::from_lines([
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]);
+        let games = Game::parse_many(&input)?;
+        let limits = minimum_limits(&games);
+        assert_eq!(limits, Hand::new(20, 13, 15));
+        assert!(games.iter().all(|game| game.is_valid(&limits)));
+        Ok(())
+    }
+}
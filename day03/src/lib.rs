@@ -0,0 +1,645 @@
+use itertools::Itertools;
+
+use util::{Adjacency, Input, Point};
+
+/// Get all Numbers in an input grid
+pub fn get_numbers_from_input(input: &Input) -> impl Iterator<Item=Number> + '_ {
+    input
+        .as_lines()
+        .enumerate()
+        .flat_map(|(row, line)| Number::parse_row(row, line))
+}
+
+/// Get all Symbols in an input grid
+pub fn get_symbols_from_input(input: &Input) -> impl Iterator<Item=Symbol> + '_ {
+    input
+        .as_lines()
+        .enumerate()
+        .flat_map(|(row, line)| Symbol::parse_row(row, line))
+}
+
+pub fn get_part_numbers(input: &Input, adjacency: Adjacency) -> Vec<u32> {
+    let input = input.trim_trailing_newlines();
+    let symbols = get_symbols_from_input(&input).collect_vec();
+    // Find all numbers that are adjacent to at least one symbol
+    get_numbers_from_input(&input)
+        .filter(|number| {
+            symbols
+                .iter()
+                .any(|symbol| number.is_adjacent_by(symbol, adjacency))
+        })
+        .map(|number| number.number)
+        .collect_vec()
+}
+
+/// Get the same part numbers as get_part_numbers, but only compare each Number with the symbols
+/// on its own row and the rows directly above and below
+pub fn get_part_numbers_scanline(input: &Input, adjacency: Adjacency) -> Vec<u32> {
+    let input = input.trim_trailing_newlines();
+    // Symbols are parsed row by row, so each row can be collected into its own bucket
+    let mut symbols_by_row: Vec<Vec<Symbol>> = Vec::new();
+    for symbol in get_symbols_from_input(&input) {
+        if symbols_by_row.len() <= symbol.row {
+            symbols_by_row.resize_with(symbol.row + 1, Vec::new);
+        }
+        symbols_by_row[symbol.row].push(symbol);
+    }
+    get_numbers_from_input(&input)
+        .filter(|number| {
+            let first = number.line.saturating_sub(1);
+            let last = (number.line + 2).min(symbols_by_row.len());
+            symbols_by_row
+                .get(first..last)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .any(|symbol| number.is_adjacent_by(symbol, adjacency))
+        })
+        .map(|number| number.number)
+        .collect_vec()
+}
+
+pub fn get_gear_ratios(input: &Input, adjacency: Adjacency) -> Vec<u32> {
+    locate_gears(input, adjacency)
+        .into_iter()
+        .map(|(_position, ratio)| ratio)
+        .collect_vec()
+}
+
+/// Get the (row, column) position of each gear together with its gear ratio
+pub fn get_gear_ratios_located(input: &Input) -> Vec<((usize, usize), u32)> {
+    locate_gears(input, Adjacency::Diagonal)
+}
+
+pub fn locate_gears(input: &Input, adjacency: Adjacency) -> Vec<((usize, usize), u32)> {
+    let input = input.trim_trailing_newlines();
+    let numbers = get_numbers_from_input(&input).collect_vec();
+    get_symbols_from_input(&input)
+        // Find all * symbols
+        .filter(|symbol| symbol.symbol == '*')
+        // For each * symbol, find all adjacent Numbers and try to collect them into a (Number, Number) tuple
+        // This will only be Some if exactly two Numbers are found and None otherwise
+        // Each Number is a separate entry in numbers, so a Number touching the symbol with several
+        // of its digits is still only counted once
+        .filter_map(|symbol| {
+            numbers
+                .iter()
+                .filter(|number| number.is_adjacent_by(&symbol, adjacency))
+                .collect_tuple()
+                .map(|gears: (&Number, &Number)| ((symbol.row, symbol.col), gears))
+        })
+        // Calculate the gear ratio for each pair of Numbers
+        .map(|(position, gears)| (position, gears.0.number * gears.1.number))
+        .collect_vec()
+}
+
+/// Report each symbol with its position and the number of Numbers adjacent to it
+pub fn symbol_adjacency_report(input: &Input) -> Vec<(char, usize, usize, usize)> {
+    let input = input.trim_trailing_newlines();
+    let numbers = get_numbers_from_input(&input).collect_vec();
+    get_symbols_from_input(&input)
+        .map(|symbol| {
+            let adjacent = numbers
+                .iter()
+                .filter(|number| number.is_adjacent_by(&symbol, Adjacency::Diagonal))
+                .count();
+            (symbol.symbol, symbol.row, symbol.col, adjacent)
+        })
+        .collect_vec()
+}
+
+pub struct Symbol {
+    symbol: char,
+    row: usize,
+    col: usize,
+}
+
+impl Symbol {
+    fn parse_row(row: usize, line: &str) -> Vec<Symbol> {
+        line.chars()
+            .enumerate()
+            .filter(|(_col, c)| !c.is_ascii_digit() && *c != '.')
+            .map(|(col, c)| Symbol {
+                symbol: c,
+                row,
+                col,
+            })
+            .collect_vec()
+    }
+}
+
+/// The direction a Number is written in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+pub struct Number {
+    number: u32,
+    orientation: Orientation,
+    /// The row of a horizontal number or the column of a vertical number
+    line: usize,
+    /// The first position of the number along its line
+    start: usize,
+    /// The last position of the number along its line
+    end: usize,
+}
+
+impl Number {
+    /// Check if a symbol is adjacent to this number, including diagonally
+    pub fn is_adjacent(&self, symbol: &Symbol) -> bool {
+        self.is_adjacent_by(symbol, Adjacency::Diagonal)
+    }
+
+    /// Check if a symbol is adjacent to any digit of this number
+    pub fn is_adjacent_by(&self, symbol: &Symbol, adjacency: Adjacency) -> bool {
+        // Transpose the symbol for vertical numbers so that we can always compare along the line
+        let (symbol_line, symbol_position) = match self.orientation {
+            Orientation::Horizontal => (symbol.row, symbol.col),
+            Orientation::Vertical => (symbol.col, symbol.row),
+        };
+        match adjacency {
+            Adjacency::Diagonal => {
+                self.line.abs_diff(symbol_line) <= 1
+                    && self.start.saturating_sub(1) <= symbol_position
+                    && symbol_position <= self.end.saturating_add(1)
+            }
+            // Either directly before or after the number on the same line,
+            // or beside one of the digits on a neighbouring line
+            Adjacency::Orthogonal => match self.line.abs_diff(symbol_line) {
+                0 => symbol_position + 1 == self.start || symbol_position == self.end + 1,
+                1 => self.start <= symbol_position && symbol_position <= self.end,
+                _ => false,
+            },
+        }
+    }
+
+    /// Get the position of each digit of this number
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.start..=self.end).map(|position| match self.orientation {
+            Orientation::Horizontal => Point::new(self.line as i64, position as i64),
+            Orientation::Vertical => Point::new(position as i64, self.line as i64),
+        })
+    }
+
+    pub fn parse_row(row: usize, line: &str) -> Vec<Number> {
+        Number::parse_line(Orientation::Horizontal, row, line)
+    }
+
+    /// Parse the vertical numbers in a column, given as the characters of the column from top to bottom
+    pub fn parse_col(col: usize, column: &str) -> Vec<Number> {
+        Number::parse_line(Orientation::Vertical, col, column)
+    }
+
+    fn parse_line(orientation: Orientation, line_index: usize, line: &str) -> Vec<Number> {
+        // Group all characters together with their position and collect into a vec
+        let indexed_chars = line.chars()
+            .enumerate()
+            .collect_vec();
+        // Split all chars into consecutive runs of ASCII digits, then parse each group into a number
+        indexed_chars.split(|(_position, c)| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .map(|number| {
+                let start = number.first().expect("Size already checked").0;
+                let end = number.last().expect("Size already checked").0;
+                let number = number.iter()
+                    .map(|(_position, c)| c)
+                    .collect::<String>().parse().expect("Only ascii digits from split");
+                Number {
+                    number,
+                    orientation,
+                    line: line_index,
+                    start,
+                    end
+                }
+            })
+            .collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        get_gear_ratios, get_gear_ratios_located, get_part_numbers, get_part_numbers_scanline,
+        symbol_adjacency_report, Number, Orientation, Symbol,
+    };
+    use rstest::rstest;
+    use util::{Adjacency, Input, Point};
+
+    #[rstest]
+    #[case(2, 4)]
+    #[case(2, 7)]
+    #[case(2, 6)]
+    #[case(1, 3)]
+    #[case(3, 3)]
+    #[case(1, 7)]
+    #[case(3, 7)]
+    #[case(3, 5)]
+    #[case(1, 5)]
+    pub fn test_is_adjacent(#[case] symbol_row: usize, #[case] symbol_col: usize) {
+        // Given a number
+        let number = Number {
+            number: 1,
+            orientation: Orientation::Horizontal,
+            line: 2,
+            start: 4,
+            end: 6,
+        };
+
+        // and a symbol
+        let symbol = Symbol {
+            symbol: '*',
+            row: symbol_row,
+            col: symbol_col,
+        };
+
+        // Expect them to be adjacent
+        assert!(number.is_adjacent(&symbol));
+    }
+
+    #[test]
+    pub fn test_is_adjacent_starts_at_zero() {
+        // Given a number
+        let number = Number {
+            number: 1,
+            orientation: Orientation::Horizontal,
+            line: 0,
+            start: 0,
+            end: 6,
+        };
+
+        // and a symbol
+        let symbol = Symbol {
+            symbol: '*',
+            row: 1,
+            col: 1,
+        };
+
+        // Expect them to be adjacent
+        assert!(number.is_adjacent(&symbol));
+    }
+
+    #[rstest]
+    #[case(2, 2)]
+    #[case(1, 2)]
+    #[case(0, 4)]
+    #[case(4, 4)]
+    pub fn test_is_not_adjacent(#[case] symbol_row: usize, #[case] symbol_col: usize) {
+        // Given a number
+        let number = Number {
+            number: 1,
+            orientation: Orientation::Horizontal,
+            line: 2,
+            start: 4,
+            end: 6,
+        };
+
+        // and a symbol
+        let symbol = Symbol {
+            symbol: '*',
+            row: symbol_row,
+            col: symbol_col,
+        };
+
+        // Expect them to be adjacent
+        assert!(!number.is_adjacent(&symbol));
+    }
+
+    #[rstest]
+    #[case(0, 2)]
+    #[case(3, 2)]
+    #[case(1, 1)]
+    #[case(2, 3)]
+    #[case(0, 1)]
+    #[case(3, 3)]
+    pub fn test_vertical_is_adjacent(#[case] symbol_row: usize, #[case] symbol_col: usize) {
+        // Given a vertical number
+        let number = Number::parse_col(2, ".12.")
+            .pop()
+            .expect("Column contains a number");
+
+        // and a symbol
+        let symbol = Symbol {
+            symbol: '*',
+            row: symbol_row,
+            col: symbol_col,
+        };
+
+        // Expect them to be adjacent
+        assert!(number.is_adjacent(&symbol));
+    }
+
+    #[rstest]
+    #[case(0, 2)]
+    #[case(5, 2)]
+    #[case(2, 0)]
+    #[case(2, 4)]
+    pub fn test_vertical_is_not_adjacent(#[case] symbol_row: usize, #[case] symbol_col: usize) {
+        // Given a vertical number
+        let number = Number::parse_col(2, "..12")
+            .pop()
+            .expect("Column contains a number");
+
+        // and a symbol
+        let symbol = Symbol {
+            symbol: '*',
+            row: symbol_row,
+            col: symbol_col,
+        };
+
+        // Expect them not to be adjacent
+        assert!(!number.is_adjacent(&symbol));
+    }
+
+    #[rstest]
+    #[case(Number::parse_row(2, "..123."), vec![(2, 2), (2, 3), (2, 4)])]
+    #[case(Number::parse_col(2, "..123."), vec![(2, 2), (3, 2), (4, 2)])]
+    pub fn test_cells(#[case] mut numbers: Vec<Number>, #[case] expected: Vec<(i64, i64)>) {
+        // Given a three digit number
+        let number = numbers.pop().expect("Line contains a number");
+
+        // When the cells are retrieved
+        let cells = number.cells().collect::<Vec<_>>();
+
+        // Then there is one consecutive cell per digit
+        assert_eq!(
+            cells,
+            expected
+                .into_iter()
+                .map(|(row, col)| Point::new(row, col))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_adjacency_via_cells_matches_is_adjacent() {
+        // Given a horizontal and a vertical number
+        let numbers = [
+            Number::parse_row(2, "....123.."),
+            Number::parse_col(2, "....123.."),
+        ];
+
+        for number in numbers.iter().flatten() {
+            // Expect adjacency by cell distance to agree with is_adjacent for every symbol position
+            for (row, col) in (0..9).flat_map(|row| (0..9).map(move |col| (row, col))) {
+                let symbol = Symbol {
+                    symbol: '*',
+                    row,
+                    col,
+                };
+                let by_cells = number
+                    .cells()
+                    .any(|cell| cell.chebyshev(&Point::new(row as i64, col as i64)) <= 1);
+                assert_eq!(
+                    by_cells,
+                    number.is_adjacent(&symbol),
+                    "Symbol at {}, {}",
+                    row,
+                    col
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parse_col() {
+        // When a column is parsed
+        let numbers = Number::parse_col(3, "1.23.");
+
+        // Then the numbers are vertical and span the expected rows
+        let numbers = numbers
+            .iter()
+            .map(|number| {
+                (
+                    number.number,
+                    number.orientation,
+                    number.line,
+                    number.start,
+                    number.end,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            numbers,
+            vec![
+                (1, Orientation::Vertical, 3, 0, 0),
+                (23, Orientation::Vertical, 3, 2, 3),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_get_part_numbers() {
+        // Given some input
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+            "......+321",
+        ]);
+
+        // When the part numbers are extracted
+        let numbers = get_part_numbers(&input, Adjacency::Diagonal);
+
+        // Then they are as expected
+        assert_eq!(numbers, vec![467, 35, 633, 617, 592, 755, 664, 598, 321])
+    }
+
+    #[rstest]
+    #[case(Adjacency::Diagonal)]
+    #[case(Adjacency::Orthogonal)]
+    pub fn test_get_part_numbers_scanline(#[case] adjacency: Adjacency) {
+        // Given some input
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+            "......+321",
+        ]);
+
+        // When the part numbers are extracted by scanning the neighbouring rows
+        let numbers = get_part_numbers_scanline(&input, adjacency);
+
+        // Then they are the same as when comparing with every symbol
+        assert_eq!(numbers, get_part_numbers(&input, adjacency))
+    }
+
+    #[test]
+    pub fn test_get_part_numbers_scanline_on_random_grids() {
+        // A small linear congruential generator so that the grids are the same on every run
+        let mut seed: u64 = 2023;
+        let mut next = move |bound: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+        for _ in 0..50 {
+            // Given a random grid of mostly dots and digits with a few symbols
+            let lines = (0..20)
+                .map(|_| {
+                    (0..20)
+                        .map(|_| match next(10) {
+                            0..=5 => '.',
+                            6..=8 => char::from_digit(next(10) as u32, 10).unwrap(),
+                            _ => ['*', '#', '$', '+'][next(4) as usize],
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            let input = Input::from_lines(lines);
+
+            // Expect both implementations to find the same part numbers
+            assert_eq!(
+                get_part_numbers_scanline(&input, Adjacency::Diagonal),
+                get_part_numbers(&input, Adjacency::Diagonal)
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(Adjacency::Diagonal, vec![1, 3])]
+    #[case(Adjacency::Orthogonal, vec![1])]
+    pub fn test_get_part_numbers_by_adjacency(
+        #[case] adjacency: Adjacency,
+        #[case] expected: Vec<u32>,
+    ) {
+        // Given a grid where 1 touches a symbol orthogonally and 3 only diagonally
+        let input = Input::from_lines(["1*..", "...3", "..#."]);
+
+        // When the part numbers are extracted
+        let numbers = get_part_numbers(&input, adjacency);
+
+        // Then they are as expected
+        assert_eq!(numbers, expected)
+    }
+
+    #[rstest]
+    #[case(Adjacency::Diagonal, vec![6, 20])]
+    #[case(Adjacency::Orthogonal, vec![6])]
+    pub fn test_get_gear_ratios_by_adjacency(
+        #[case] adjacency: Adjacency,
+        #[case] expected: Vec<u32>,
+    ) {
+        // Given a grid with one orthogonal gear and one diagonal gear
+        let input = Input::from_lines(["2*3", "...", "4..", ".*.", "..5"]);
+
+        // When the gear ratios are retrieved
+        let ratios = get_gear_ratios(&input, adjacency);
+
+        // Then they are as expected
+        assert_eq!(ratios, expected)
+    }
+
+    #[test]
+    pub fn test_get_gear_ratios() {
+        // Given som input
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]);
+
+        // When the gear ratios is retrieved
+        let ratios = get_gear_ratios(&input, Adjacency::Diagonal);
+
+        // Then they are as expected
+        assert_eq!(ratios, vec![16345, 451490])
+    }
+
+    #[test]
+    pub fn test_get_gear_ratios_located() {
+        // Given some input
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]);
+
+        // When the gears are located
+        let gears = get_gear_ratios_located(&input);
+
+        // Then the two gears are found with their ratios
+        assert_eq!(gears, vec![((1, 3), 16345), ((8, 5), 451490)])
+    }
+
+    #[rstest]
+    #[case(vec!["12*34"], vec![((0, 2), 408)])]
+    #[case(vec!["123", ".*.", ".4."], vec![((1, 1), 492)])]
+    #[case(vec!["..5..", "12*34"], vec![])]
+    pub fn test_get_gear_ratios_counts_each_number_once(
+        #[case] lines: Vec<&str>,
+        #[case] expected: Vec<((usize, usize), u32)>,
+    ) {
+        // Given a * flanked by numbers, some touching it with several digits
+        let input = Input::from_lines(lines);
+
+        // When the gears are located
+        let gears = get_gear_ratios_located(&input);
+
+        // Then each number is only counted once per symbol
+        assert_eq!(gears, expected)
+    }
+
+    #[test]
+    pub fn test_symbol_adjacency_report() {
+        // Given some input
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]);
+
+        // When the symbol adjacency report is created
+        let report = symbol_adjacency_report(&input);
+
+        // Then only the two gears have two adjacent numbers
+        assert_eq!(
+            report,
+            vec![
+                ('*', 1, 3, 2),
+                ('#', 3, 6, 1),
+                ('*', 4, 3, 1),
+                ('+', 5, 5, 1),
+                ('$', 8, 3, 1),
+                ('*', 8, 5, 2),
+            ]
+        )
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use std::str::FromStr;
+
+use util::{Input, Result};
+
+/// Count the total number of cards we end up with, including all won copies
+///
+/// Fails if the count doesn't fit in a u64
+pub fn total_cards(cards: &[Card]) -> Result<u64> {
+    // Sum the number of cards we have
+    card_copy_counts(cards)?
+        .iter()
+        .try_fold(0u64, |total, count| total.checked_add(*count))
+        .ok_or_else(|| anyhow!("Too many cards in total"))
+}
+
+/// Count how many copies we end up with of each card, including the original
+///
+/// Fails if the count of any card doesn't fit in a u64
+pub fn card_copy_counts(cards: &[Card]) -> Result<Vec<u64>> {
+    // Vector to keep track of how many we have of each card
+    let mut card_counts = vec![1u64; cards.len()];
+
+    // Go through each card, adding copies of each card that comes after if we win
+    for (index, card) in cards.iter().enumerate() {
+        let matches = card.matches();
+        let next = index + 1;
+        let last = (next + matches).min(cards.len());
+
+        // Add the number of instances of this card to each following card
+        // i.e. 2 copies of card 2 with 2 matches adds 2 more copies of card 3 and 4
+        for add_index in next..last {
+            card_counts[add_index] = card_counts[add_index]
+                .checked_add(card_counts[index])
+                .ok_or_else(|| anyhow!("Too many copies of card {}", add_index + 1))?;
+        }
+    }
+    Ok(card_counts)
+}
+
+/// Count the total number of winning numbers and owned numbers across all cards
+pub fn input_stats(input: &Input) -> Result<(usize, usize)> {
+    input
+        .trim_trailing_newlines()
+        .as_lines()
+        .map(Card::from_str)
+        .fold_ok((0, 0), |(winners, numbers), card| {
+            (winners + card.winners.len(), numbers + card.numbers.len())
+        })
+}
+
+/// Count the total number of cards we end up with by recursively counting
+/// how many cards each card yields, remembering the count for each card
+///
+/// Fails if the count doesn't fit in a u64
+pub fn count_with_memo(cards: &[Card]) -> Result<u64> {
+    fn yielded(index: usize, cards: &[Card], memo: &mut Vec<Option<u64>>) -> Result<u64> {
+        if let Some(count) = memo[index] {
+            return Ok(count);
+        }
+        let last = (index + 1 + cards[index].matches()).min(cards.len());
+        // This card plus everything yielded by the copies it wins
+        let count = (index + 1..last).try_fold(1u64, |count, won| {
+            count
+                .checked_add(yielded(won, cards, memo)?)
+                .ok_or_else(|| anyhow!("Too many cards yielded by card {}", index + 1))
+        })?;
+        memo[index] = Some(count);
+        Ok(count)
+    }
+
+    let mut memo = vec![None; cards.len()];
+    (0..cards.len()).try_fold(0u64, |total, index| {
+        total
+            .checked_add(yielded(index, cards, &mut memo)?)
+            .ok_or_else(|| anyhow!("Too many cards in total"))
+    })
+}
+
+/// Find the index of the first card without any matches
+pub fn first_losing_card(cards: &[Card]) -> Option<usize> {
+    cards.iter().position(|card| card.matches() == 0)
+}
+
+/// Count how many cards at the start all have at least one match
+pub fn winning_streak_len(cards: &[Card]) -> usize {
+    first_losing_card(cards).unwrap_or(cards.len())
+}
+
+pub struct Card {
+    winners: HashSet<u32>,
+    numbers: HashSet<u32>,
+}
+
+impl Card {
+    /// Calculate the number of matches for this card
+    pub fn matches(&self) -> usize {
+        self.matches_where(|_| true)
+    }
+
+    /// Calculate the number of matches for this card that also satisfy a predicate
+    pub fn matches_where(&self, pred: impl Fn(u32) -> bool) -> usize {
+        self.winners
+            .intersection(&self.numbers)
+            .filter(|number| pred(**number))
+            .count()
+    }
+
+    /// Calculate the score for this card
+    pub fn score(&self) -> u64 {
+        self.score_with(1)
+    }
+
+    /// Calculate the score for this card where the first match is worth `base` points
+    /// and every match after that doubles the score
+    pub fn score_with(&self, base: u64) -> u64 {
+        match self.matches() {
+            0 => 0,
+            matches => base * 2u64.pow(matches as u32 - 1),
+        }
+    }
+}
+
+impl FromStr for Card {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (declaration, winners, numbers) = s
+            .split(&['|', ':'])
+            .collect_tuple()
+            .ok_or_else(|| anyhow!("Invalid card: `{}`", s))?;
+        // The id may be glued to the prefix, as in "Card1"
+        declaration
+            .trim()
+            .strip_prefix("Card")
+            .and_then(|id| id.trim().parse::<u32>().ok())
+            .ok_or_else(|| anyhow!("Invalid card declaration: `{}`", declaration))?;
+        let winners: HashSet<u32> = winners
+            .split_whitespace()
+            .map(|n| n.parse())
+            .try_collect()?;
+        let numbers: HashSet<u32> = numbers
+            .split_whitespace()
+            .map(|n| n.parse())
+            .try_collect()?;
+        Ok(Card { winners, numbers })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        card_copy_counts, count_with_memo, first_losing_card, input_stats, total_cards,
+        winning_streak_len, Card,
+    };
+    use itertools::Itertools;
+    use rstest::rstest;
+    use std::str::FromStr;
+    use util::{Input, Result};
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 8)]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", 2)]
+    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", 2)]
+    #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", 1)]
+    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0)]
+    #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", 0)]
+    pub fn test_get_card_score(#[case] card: &str, #[case] expected_score: u64) {
+        // Given a card
+        let card = Card::from_str(card).unwrap();
+
+        // Expect the cards score to be correct
+        assert_eq!(card.score(), expected_score);
+    }
+
+    #[rstest]
+    #[case("Card1:41 48 83 86 17|83 86 6 31 17 9 48 53")]
+    #[case("Card 1:41 48 83 86 17|83 86  6 31 17  9 48 53")]
+    #[case("Card  1 : 41 48 83 86 17 | 83 86  6 31 17  9 48 53 ")]
+    pub fn test_parse_compact_card(#[case] card: &str) {
+        // When a card without spaces around the delimiters is parsed
+        let card = Card::from_str(card).unwrap();
+
+        // Then it is parsed like the spaced out card
+        assert_eq!(card.matches(), 4);
+        assert_eq!(card.score(), 8);
+    }
+
+    #[test]
+    pub fn test_matches_where() -> Result<()> {
+        // Given a card where 48, 83, 86 and 17 match
+        let card = Card::from_str("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")?;
+
+        // Expect only 48 and 86 to be even matches
+        assert_eq!(card.matches_where(|number| number % 2 == 0), 2);
+        assert_eq!(card.matches_where(|_| true), card.matches());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Crd 1: 41 48 | 83 86")]
+    #[case("Card: 41 48 | 83 86")]
+    #[case("Card x1: 41 48 | 83 86")]
+    pub fn test_parse_card_with_invalid_declaration(#[case] card: &str) {
+        // When a card with a broken declaration is parsed
+        let card = Card::from_str(card);
+
+        // Then it is an error
+        assert!(card.is_err());
+    }
+
+    #[rstest]
+    #[case("Card 1: 1 2 3 4 | 1 2 3 9", 1, 4)]
+    #[case("Card 1: 1 2 3 4 | 1 2 3 9", 3, 12)]
+    #[case("Card 1: 1 2 3 4 | 1 2 5 9", 3, 6)]
+    #[case("Card 1: 1 2 3 4 | 5 6 7 8", 3, 0)]
+    pub fn test_get_card_score_with_base(
+        #[case] card: &str,
+        #[case] base: u64,
+        #[case] expected_score: u64,
+    ) {
+        // Given a card
+        let card = Card::from_str(card).unwrap();
+
+        // Expect the cards score with the base to be correct
+        assert_eq!(card.score_with(base), expected_score);
+    }
+
+    #[test]
+    pub fn test_input_stats() -> Result<()> {
+        let input = Input::from_lines([
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]);
+        assert_eq!(input_stats(&input)?, (6 * 5, 6 * 8));
+        Ok(())
+    }
+
+    fn sample_cards() -> Result<Vec<Card>> {
+        [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .into_iter()
+        .map(Card::from_str)
+        .collect()
+    }
+
+    #[test]
+    pub fn test_card_copy_counts() -> Result<()> {
+        // Given the sample cards
+        let cards = sample_cards()?;
+
+        // Expect the number of copies of each card to add up to the total
+        assert_eq!(card_copy_counts(&cards)?, vec![1, 2, 4, 8, 14, 1]);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_count_with_memo_equals_total_cards() -> Result<()> {
+        // Given the sample cards
+        let cards = sample_cards()?;
+
+        // Expect the memoized recursion to count the same number of cards as the iteration
+        assert_eq!(count_with_memo(&cards)?, 30);
+        assert_eq!(count_with_memo(&cards)?, total_cards(&cards)?);
+
+        // And to fail just like the iteration when the total doesn't fit in a u64
+        let cards = all_winning_cards(65)?;
+        assert!(count_with_memo(&cards).is_err());
+        assert!(total_cards(&cards).is_err());
+        Ok(())
+    }
+
+    /// Create cards where each card wins a copy of every card after it
+    fn all_winning_cards(count: usize) -> Result<Vec<Card>> {
+        (0..count)
+            .map(|index| {
+                let numbers = (1..count - index).map(|n| n.to_string()).join(" ");
+                Card::from_str(&format!("Card {}: {} | {}", index + 1, numbers, numbers))
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn test_total_cards_with_long_winning_run() -> Result<()> {
+        // Given 40 cards where each card doubles the copies of every card after it
+        let cards = all_winning_cards(40)?;
+
+        // Expect the total to be counted beyond the range of a u32
+        assert_eq!(total_cards(&cards)?, 2u64.pow(40) - 1);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_total_cards_overflow() -> Result<()> {
+        // Given so many winning cards that the total doesn't fit in a u64
+        let cards = all_winning_cards(65)?;
+
+        // Expect counting them to fail instead of wrapping
+        assert!(total_cards(&cards).is_err());
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_first_losing_card() -> Result<()> {
+        // Given the sample cards
+        let cards = sample_cards()?;
+
+        // Expect card 5 to be the first card without matches, after a streak of four winners
+        assert_eq!(first_losing_card(&cards), Some(4));
+        assert_eq!(winning_streak_len(&cards), 4);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_first_losing_card_when_all_win() -> Result<()> {
+        // Given only the winning sample cards
+        let cards = &sample_cards()?[..4];
+
+        // Expect there to be no losing card and the streak to cover all cards
+        assert_eq!(first_losing_card(cards), None);
+        assert_eq!(winning_streak_len(cards), 4);
+        Ok(())
+    }
+}
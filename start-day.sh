@@ -11,13 +11,6 @@ fi
 source .env
 day=$1
 prefixed=$(printf '%02d' $day)
-target="day${prefixed}"
-cp -r template day${prefixed}
-sed -i "s/dayXX/day${prefixed}/" ${target}/Cargo.toml ${target}/src/main.rs
-
-head -n -1 Cargo.toml >Cargo.toml.new
-echo "  \"day${prefixed}\"," >>Cargo.toml.new
-echo "]" >>Cargo.toml.new
-mv Cargo.toml.new Cargo.toml
+cargo run -q -p xtask -- ${day} || exit 1
 
 exec curl "https://adventofcode.com/2023/day/${1}/input" -H "Cookie: session=${AOC_SESSION}" -o "day${prefixed}/input"
@@ -1,9 +1,9 @@
-use anyhow::{anyhow, Result};
+use anyhow::anyhow;
 
-use util::Input;
+use util::{Input, Result};
 
 fn main() -> Result<()> {
-    let input = Input::load("dayXX/input")?;
+    let input = Input::from_arg_or_stdin()?;
 
     println!("Part 1:");
     println!("{}", part1(&input)?);
@@ -25,8 +25,7 @@ fn part2(input: &Input) -> Result<u32> {
 #[cfg(test)]
 mod test {
     use crate::{part1, part2};
-    use anyhow::Result;
-    use util::Input;
+    use util::{Input, Result};
 
     /*
     #[test]
@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+
+/// An error while solving a puzzle, optionally pointing at where in the input it occurred
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    message: String,
+    location: Option<(usize, usize)>,
+}
+
+impl AocError {
+    pub fn new(message: impl Into<String>) -> AocError {
+        AocError {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Attach the line and column in the input where the error occurred
+    ///
+    /// Both are 0-based like the indices of lines and characters, but displayed 1-based
+    pub fn at(self, line: usize, column: usize) -> AocError {
+        AocError {
+            location: Some((line, column)),
+            ..self
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> Option<(usize, usize)> {
+        self.location
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(
+                f,
+                "line {}, column {}: {}",
+                line + 1,
+                column + 1,
+                self.message
+            ),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<anyhow::Error> for AocError {
+    fn from(error: anyhow::Error) -> AocError {
+        // Use the alternate format to keep the context chain in the message
+        AocError::new(format!("{:#}", error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AocError;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn test_display_with_location() {
+        // given an error with a location
+        let error = AocError::new("Unexpected character '#'").at(3, 7);
+
+        // expect the location to be part of the message, counted from 1
+        assert_eq!(
+            "line 4, column 8: Unexpected character '#'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_without_location() {
+        // given an error without a location
+        let error = AocError::new("Empty input");
+
+        // expect only the message
+        assert_eq!("Empty input", error.to_string());
+        assert_eq!(None, error.location());
+    }
+
+    #[test]
+    fn test_from_anyhow() {
+        // given an anyhow error with context
+        let error = Err::<(), _>(anyhow!("Not a number"))
+            .context("Invalid card")
+            .unwrap_err();
+
+        // when it is converted
+        let error = AocError::from(error);
+
+        // then the whole chain is kept
+        assert_eq!("Invalid card: Not a number", error.message());
+    }
+
+    #[test]
+    fn test_into_anyhow() {
+        // expect an AocError to be usable with ?
+        fn fails() -> crate::Result<()> {
+            Err(AocError::new("Failed").at(1, 2))?
+        }
+        assert_eq!("line 2, column 3: Failed", fails().unwrap_err().to_string());
+    }
+}
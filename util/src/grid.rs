@@ -0,0 +1,802 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt::Display;
+
+use crate::Input;
+
+/// Which cells count as adjacent to a cell
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Adjacency {
+    /// Only the cells directly above, below, left and right
+    Orthogonal,
+    /// The orthogonal cells as well as the diagonal ones
+    Diagonal,
+}
+
+/// A rectangular grid of cells addressed by (row, column)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// Create a grid with one cell per character of the input
+    ///
+    /// Panics if the lines of the input are not all of the same length
+    pub fn from_input(input: &Input) -> Grid<char> {
+        let input = input.trim_trailing_newlines();
+        let mut lines = input.as_lines().filter(|line| !line.is_empty()).peekable();
+        let width = lines.peek().map_or(0, |line| line.chars().count());
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in lines {
+            let before = cells.len();
+            cells.extend(line.chars());
+            assert_eq!(
+                cells.len() - before,
+                width,
+                "Line {} has the wrong width",
+                height
+            );
+            height += 1;
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Create a grid with one cell per byte of the input, avoiding char decoding for ASCII input
+    ///
+    /// Panics if the lines of the input are not all of the same length
+    pub fn from_bytes(input: &Input) -> Grid<u8> {
+        let input = input.trim_trailing_newlines();
+        let mut lines = input
+            .as_str()
+            .as_bytes()
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .peekable();
+        let width = lines.peek().map_or(0, |line| line.len());
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in lines {
+            assert_eq!(line.len(), width, "Line {} has the wrong width", height);
+            cells.extend_from_slice(line);
+            height += 1;
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Create a grid where each cell is initialized by calling `f` with its row and column
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| f(row, col))
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the cell at the given position, or None if it is outside the grid
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.height && col < self.width {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// Count the cells that satisfy a predicate
+    pub fn count(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| pred(cell)).count()
+    }
+
+    /// Iterate over the cells on the border of the grid, each cell exactly once
+    pub fn edge_cells(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (index / self.width, index % self.width, cell))
+            .filter(|(row, col, _)| {
+                *row == 0 || *col == 0 || *row + 1 == self.height || *col + 1 == self.width
+            })
+    }
+
+    /// Get the positions of the orthogonal neighbours of a cell that are inside the grid
+    pub fn neighbors4(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        self.neighbors4_opt(row, col)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Get the positions of the orthogonal neighbours of a cell in north, east, south, west order,
+    /// with None for neighbours that are outside the grid
+    pub fn neighbors4_opt(&self, row: usize, col: usize) -> [Option<(usize, usize)>; 4] {
+        [
+            row.checked_sub(1).map(|row| (row, col)),
+            Some((row, col + 1)).filter(|_| col + 1 < self.width),
+            Some((row + 1, col)).filter(|_| row + 1 < self.height),
+            col.checked_sub(1).map(|col| (row, col)),
+        ]
+    }
+
+    /// Get the positions of the orthogonal neighbours of a cell in north, east, south, west order,
+    /// treating the grid as a torus where the edges wrap around to the opposite side
+    pub fn neighbors4_wrapping(&self, row: usize, col: usize) -> [(usize, usize); 4] {
+        [
+            ((row + self.height - 1) % self.height, col),
+            (row, (col + 1) % self.width),
+            ((row + 1) % self.height, col),
+            (row, (col + self.width - 1) % self.width),
+        ]
+    }
+
+    /// Call `f` with the position and value of each neighbour of a cell that is inside the grid,
+    /// including the diagonal neighbours if `diagonal` is true, without allocating
+    pub fn for_each_neighbor(
+        &self,
+        row: usize,
+        col: usize,
+        diagonal: bool,
+        mut f: impl FnMut(usize, usize, &T),
+    ) {
+        for (row_delta, col_delta) in [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ] {
+            if !diagonal && row_delta != 0 && col_delta != 0 {
+                continue;
+            }
+            let (Some(next_row), Some(next_col)) = (
+                row.checked_add_signed(row_delta),
+                col.checked_add_signed(col_delta),
+            ) else {
+                continue;
+            };
+            if let Some(cell) = self.get(next_row, next_col) {
+                f(next_row, next_col, cell);
+            }
+        }
+    }
+
+    /// Find the cheapest path between two cells using orthogonal moves,
+    /// where moving into a cell costs `cost` of that cell
+    ///
+    /// Returns None if the start is outside the grid or the goal can't be reached
+    pub fn dijkstra(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        cost: impl Fn(&T) -> u64,
+    ) -> Option<u64> {
+        self.get(start.0, start.1)?;
+        let mut best = vec![u64::MAX; self.cells.len()];
+        let mut queue = BinaryHeap::new();
+        best[start.0 * self.width + start.1] = 0;
+        queue.push(Reverse((0, start)));
+
+        while let Some(Reverse((total, (row, col)))) = queue.pop() {
+            if (row, col) == goal {
+                return Some(total);
+            }
+            // Skip stale entries that have already been reached more cheaply
+            if total > best[row * self.width + col] {
+                continue;
+            }
+            for (next_row, next_col) in self.neighbors4(row, col) {
+                let index = next_row * self.width + next_col;
+                let next_total = total + cost(&self.cells[index]);
+                if next_total < best[index] {
+                    best[index] = next_total;
+                    queue.push(Reverse((next_total, (next_row, next_col))));
+                }
+            }
+        }
+        None
+    }
+
+    /// Find the cheapest path between two cells like [Grid::dijkstra], but explore the cells
+    /// that `heuristic` estimates to be closest to the goal first
+    ///
+    /// The heuristic must never overestimate the remaining cost from a cell to the goal,
+    /// or the path found may not be the cheapest
+    ///
+    /// Returns None if the start is outside the grid or the goal can't be reached
+    pub fn astar(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        cost: impl Fn(&T) -> u64,
+        heuristic: impl Fn((usize, usize)) -> u64,
+    ) -> Option<u64> {
+        self.get(start.0, start.1)?;
+        let mut best = vec![u64::MAX; self.cells.len()];
+        let mut queue = BinaryHeap::new();
+        best[start.0 * self.width + start.1] = 0;
+        queue.push(Reverse((heuristic(start), 0, start)));
+
+        while let Some(Reverse((_estimate, total, (row, col)))) = queue.pop() {
+            if (row, col) == goal {
+                return Some(total);
+            }
+            // Skip stale entries that have already been reached more cheaply
+            if total > best[row * self.width + col] {
+                continue;
+            }
+            for next in self.neighbors4(row, col) {
+                let index = next.0 * self.width + next.1;
+                let next_total = total + cost(&self.cells[index]);
+                if next_total < best[index] {
+                    best[index] = next_total;
+                    queue.push(Reverse((next_total + heuristic(next), next_total, next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Find the cheapest path between two cells with [Grid::astar], using the Manhattan
+    /// distance to the goal as heuristic
+    ///
+    /// The path is only guaranteed to be the cheapest if every cell costs at least 1
+    pub fn astar_manhattan(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        cost: impl Fn(&T) -> u64,
+    ) -> Option<u64> {
+        self.astar(start, goal, cost, |(row, col)| {
+            (row.abs_diff(goal.0) + col.abs_diff(goal.1)) as u64
+        })
+    }
+
+    /// Find the cells along a shortest path between two cells using orthogonal moves through
+    /// cells that are `passable`, including both the start and the goal
+    ///
+    /// Returns None if the start is outside the grid or the goal can't be reached
+    pub fn shortest_path_cells(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        passable: impl Fn(&T) -> bool,
+    ) -> Option<Vec<(usize, usize)>> {
+        self.get(start.0, start.1)?;
+        // The cell each visited cell was first reached from, the start is its own predecessor
+        let mut predecessors = vec![None; self.cells.len()];
+        let mut queue = VecDeque::new();
+        predecessors[start.0 * self.width + start.1] = Some(start);
+        queue.push_back(start);
+
+        while let Some((row, col)) = queue.pop_front() {
+            if (row, col) == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while current != start {
+                    current = predecessors[current.0 * self.width + current.1]
+                        .expect("Every visited cell has a predecessor");
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (next_row, next_col) in self.neighbors4(row, col) {
+                let index = next_row * self.width + next_col;
+                if predecessors[index].is_none() && passable(&self.cells[index]) {
+                    predecessors[index] = Some((row, col));
+                    queue.push_back((next_row, next_col));
+                }
+            }
+        }
+        None
+    }
+
+    /// Label each cell with the component it belongs to, where orthogonal neighbours are in the
+    /// same component if `connected` returns true for them
+    ///
+    /// Returns the grid of labels, numbered from 0, and the number of components
+    pub fn connected_components(&self, connected: impl Fn(&T, &T) -> bool) -> (Grid<usize>, usize) {
+        let mut labels = vec![usize::MAX; self.cells.len()];
+        let mut count = 0;
+        for start in 0..self.cells.len() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            labels[start] = count;
+            let mut stack = vec![start];
+            while let Some(index) = stack.pop() {
+                let (row, col) = (index / self.width, index % self.width);
+                for (next_row, next_col) in self.neighbors4(row, col) {
+                    let next = next_row * self.width + next_col;
+                    if labels[next] == usize::MAX
+                        && connected(&self.cells[index], &self.cells[next])
+                    {
+                        labels[next] = count;
+                        stack.push(next);
+                    }
+                }
+            }
+            count += 1;
+        }
+        let labels = Grid {
+            width: self.width,
+            height: self.height,
+            cells: labels,
+        };
+        (labels, count)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid where every cell has the same value
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Return a copy of the grid rotated 90 degrees clockwise
+    pub fn rotate_cw(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|row| {
+                (0..self.height)
+                    .map(move |col| self.cells[(self.height - 1 - col) * self.width + row].clone())
+            })
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Return a copy of the grid rotated 90 degrees counter-clockwise
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|row| {
+                (0..self.height)
+                    .map(move |col| self.cells[col * self.width + self.width - 1 - row].clone())
+            })
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Return a copy of the grid rotated 180 degrees
+    pub fn rotate_180(&self) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+impl<T: Display> Grid<T> {
+    /// Render the grid back into input with one line per row
+    pub fn to_input(&self) -> Input {
+        Input::from_lines(
+            self.cells
+                .chunks(self.width.max(1))
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect::<String>()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::Input;
+    use rstest::rstest;
+
+    fn digit_cost(c: &char) -> u64 {
+        c.to_digit(10).expect("Only digits in grid") as u64
+    }
+
+    #[test]
+    fn test_from_input() {
+        // given some input
+        let input = Input::from_lines(["abc", "def"]);
+
+        // when a grid is created from it
+        let grid = Grid::from_input(&input);
+
+        // then it has the dimensions and cells of the input
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 2), Some(&'f'));
+        assert_eq!(grid.get(2, 0), None);
+    }
+
+    #[test]
+    fn test_from_input_skips_leading_blank_line() {
+        // given some input that starts with a blank line
+        let input = Input::from_str("\nab\ncd");
+
+        // when a grid is created from it
+        let grid = Grid::from_input(&input);
+
+        // then the width is taken from the first non-blank line
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 0), Some(&'c'));
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        // given some input
+        let input = Input::from_lines(["467..", "...*.", "..35."]);
+
+        // when a byte grid is created from it
+        let grid = Grid::from_bytes(&input);
+
+        // then the cells are the bytes of the input
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid.get(2, 2), Some(&b'3'));
+        assert_eq!(grid.get(1, 3), Some(&b'*'));
+    }
+
+    #[test]
+    fn test_filled() {
+        // when a grid is created filled with a value
+        let grid = Grid::filled(3, 2, false);
+
+        // then it has the given dimensions and every cell has the value
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.edge_cells().filter(|(_, _, cell)| !**cell).count(), 6);
+        assert_eq!(grid.get(2, 0), None);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        // when a grid is created from a function of the position
+        let grid = Grid::from_fn(3, 2, |row, col| row * 10 + col);
+
+        // then each cell is initialized from its position
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(0, 2), Some(&2));
+        assert_eq!(grid.get(1, 1), Some(&11));
+    }
+
+    #[test]
+    fn test_to_input_round_trip() {
+        // given some input with a trailing newline
+        let input = Input::from_str("467..\n...*.\n..35.\n");
+
+        // expect converting it to a grid and back to give the trimmed input
+        assert_eq!(
+            Grid::from_input(&input).to_input(),
+            input.trim_trailing_newlines()
+        );
+    }
+
+    #[test]
+    fn test_to_input_of_numbers() {
+        // given a grid of numbers
+        let grid = Grid::from_fn(3, 2, |row, col| row + col);
+
+        // expect each number to be rendered in its cell
+        assert_eq!(grid.to_input(), Input::from_lines(["012", "123"]));
+    }
+
+    #[test]
+    fn test_edge_cells() {
+        // given a 3x3 grid
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def", "ghi"]));
+
+        // when the edge cells are retrieved
+        let edges = grid.edge_cells().map(|(_, _, c)| *c).collect::<String>();
+
+        // then all cells except the center are yielded once
+        assert_eq!(edges, "abcdfghi");
+    }
+
+    #[test]
+    fn test_neighbors4_opt_in_corner() {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def", "ghi"]));
+
+        // expect the top right corner to only have neighbours to the south and west
+        assert_eq!(
+            grid.neighbors4_opt(0, 2),
+            [None, None, Some((1, 2)), Some((0, 1))]
+        );
+        // and the bottom left corner to only have neighbours to the north and east
+        assert_eq!(
+            grid.neighbors4_opt(2, 0),
+            [Some((1, 0)), Some((2, 1)), None, None]
+        );
+    }
+
+    #[test]
+    fn test_rotate_cw() {
+        // given a grid that is wider than it is high
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def"]));
+
+        // when it is rotated clockwise
+        let rotated = grid.rotate_cw();
+
+        // then the dimensions are swapped and the first column is now the top row, bottom up
+        assert_eq!(
+            rotated,
+            Grid::from_input(&Input::from_lines(["da", "eb", "fc"]))
+        );
+    }
+
+    #[test]
+    fn test_rotate_ccw() {
+        // given a grid that is wider than it is high
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def"]));
+
+        // when it is rotated counter-clockwise
+        let rotated = grid.rotate_ccw();
+
+        // then the dimensions are swapped and the last column is now the top row
+        assert_eq!(
+            rotated,
+            Grid::from_input(&Input::from_lines(["cf", "be", "ad"]))
+        );
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def"]));
+
+        // expect rotating it 180 degrees to equal rotating it clockwise twice
+        assert_eq!(
+            grid.rotate_180(),
+            Grid::from_input(&Input::from_lines(["fed", "cba"]))
+        );
+        assert_eq!(grid.rotate_180(), grid.rotate_cw().rotate_cw());
+    }
+
+    #[test]
+    fn test_rotate_full_circle() {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def"]));
+
+        // expect four rotations in either direction to give back the original
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), grid);
+        assert_eq!(
+            grid.rotate_ccw().rotate_ccw().rotate_ccw().rotate_ccw(),
+            grid
+        );
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn test_dijkstra_finds_cheapest_path() {
+        // given a grid where the cheap path goes around the expensive middle
+        let grid = Grid::from_input(&Input::from_lines(["1111", "9991", "1111", "1999", "1111"]));
+
+        // when the cheapest path from the top left to the bottom right is found
+        let cost = grid.dijkstra((0, 0), (4, 3), digit_cost);
+
+        // then it follows the snake of ones
+        assert_eq!(cost, Some(13));
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheaper_detour() {
+        // given a grid where going straight is more expensive than a detour
+        let grid = Grid::from_input(&Input::from_lines(["191", "111"]));
+
+        // when the cheapest path across the top row is found
+        let cost = grid.dijkstra((0, 0), (0, 2), digit_cost);
+
+        // then the detour through the bottom row is taken
+        assert_eq!(cost, Some(4));
+    }
+
+    #[test]
+    fn test_dijkstra_to_start_is_free() {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["12", "34"]));
+
+        // expect the cost of going nowhere to be zero
+        assert_eq!(grid.dijkstra((1, 1), (1, 1), digit_cost), Some(0));
+    }
+
+    #[rstest]
+    #[case((2, 0))]
+    #[case((0, 2))]
+    #[case((5, 5))]
+    fn test_path_from_outside_grid(#[case] start: (usize, usize)) {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["12", "34"]));
+
+        // expect no path from a start outside of it
+        assert_eq!(grid.dijkstra(start, (1, 1), digit_cost), None);
+        assert_eq!(grid.astar_manhattan(start, (1, 1), digit_cost), None);
+    }
+
+    #[rstest]
+    #[case(&["1111", "9991", "1111", "1999", "1111"], (0, 0), (4, 3))]
+    #[case(&["191", "111"], (0, 0), (0, 2))]
+    #[case(&["12", "34"], (1, 1), (1, 1))]
+    #[case(&["1163751742", "1381373672", "2136511328", "3694931569", "7463417111"], (0, 0), (4, 9))]
+    fn test_astar_equals_dijkstra(
+        #[case] lines: &[&str],
+        #[case] start: (usize, usize),
+        #[case] goal: (usize, usize),
+    ) {
+        // given a grid of costs
+        let grid = Grid::from_input(&Input::from_lines(lines));
+
+        // expect A* to find a path as cheap as Dijkstra does
+        assert_eq!(
+            grid.astar_manhattan(start, goal, digit_cost),
+            grid.dijkstra(start, goal, digit_cost)
+        );
+    }
+
+    #[test]
+    fn test_astar_without_heuristic() {
+        // given a grid where the cheap path goes around the expensive middle
+        let grid = Grid::from_input(&Input::from_lines(["1111", "9991", "1111", "1999", "1111"]));
+
+        // expect A* without a heuristic to behave like Dijkstra
+        assert_eq!(grid.astar((0, 0), (4, 3), digit_cost, |_| 0), Some(13));
+    }
+
+    #[test]
+    fn test_connected_components() {
+        // given a grid with two separate regions of a's
+        let grid = Grid::from_input(&Input::from_lines(["aab", "bbb", "baa"]));
+
+        // when the grid is split into components of equal characters
+        let (labels, count) = grid.connected_components(|a, b| a == b);
+
+        // then the a's in each corner are separate components and the b's are one
+        assert_eq!(count, 3);
+        assert_eq!(labels.get(0, 0), labels.get(0, 1));
+        assert_eq!(labels.get(2, 1), labels.get(2, 2));
+        assert_ne!(labels.get(0, 0), labels.get(2, 2));
+        assert_eq!(labels.get(0, 2), labels.get(2, 0));
+    }
+
+    #[test]
+    fn test_count() {
+        // given the day 3 sample grid
+        let grid = Grid::from_input(&Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]));
+
+        // expect three gears among the six symbols
+        assert_eq!(grid.count(|c| *c == '*'), 3);
+        assert_eq!(grid.count(|c| !c.is_ascii_digit() && *c != '.'), 6);
+    }
+
+    #[test]
+    fn test_shortest_path_cells() {
+        // given a small maze
+        let grid = Grid::from_input(&Input::from_lines(["S.#", "#.#", "#..", "##G"]));
+
+        // when the shortest path from S to G is reconstructed
+        let path = grid.shortest_path_cells((0, 0), (3, 2), |c| *c != '#');
+
+        // then it contains one more cell than the five steps and runs between the endpoints
+        let path = path.expect("The goal is reachable");
+        assert_eq!(path.len(), 5 + 1);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(3, 2)));
+        assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn test_shortest_path_cells_when_blocked() {
+        // given a maze where the goal is walled off
+        let grid = Grid::from_input(&Input::from_lines(["S#.", "##G"]));
+
+        // expect there to be no path
+        assert_eq!(
+            grid.shortest_path_cells((0, 0), (1, 2), |c| *c != '#'),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case((0, 3))]
+    #[case((0, 5))]
+    #[case((9, 9))]
+    fn test_shortest_path_cells_from_outside_grid(#[case] start: (usize, usize)) {
+        // given a small maze
+        let grid = Grid::from_input(&Input::from_lines(["...", "...", "..."]));
+
+        // expect no path from a start outside of it
+        assert_eq!(grid.shortest_path_cells(start, (2, 2), |c| *c != '#'), None);
+    }
+
+    #[rstest]
+    #[case(1, 1, false, 2 + 4 + 6 + 8)]
+    #[case(1, 1, true, 1 + 2 + 3 + 4 + 6 + 7 + 8 + 9)]
+    #[case(0, 0, false, 2 + 4)]
+    #[case(0, 0, true, 2 + 4 + 5)]
+    fn test_for_each_neighbor(
+        #[case] row: usize,
+        #[case] col: usize,
+        #[case] diagonal: bool,
+        #[case] expected: u32,
+    ) {
+        // given a grid of digits
+        let grid = Grid::from_input(&Input::from_lines(["123", "456", "789"]));
+
+        // when the values of the neighbours of a cell are summed
+        let mut sum = 0;
+        grid.for_each_neighbor(row, col, diagonal, |_, _, c| sum += c.to_digit(10).unwrap());
+
+        // then only the neighbours inside the grid are included
+        assert_eq!(sum, expected);
+    }
+
+    #[test]
+    fn test_neighbors4_wrapping_in_corner() {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def", "ghi"]));
+
+        // expect the neighbours of the top left corner to wrap to the bottom and right edges
+        assert_eq!(
+            grid.neighbors4_wrapping(0, 0),
+            [(2, 0), (0, 1), (1, 0), (0, 2)]
+        );
+        // and the neighbours of the bottom right corner to wrap to the top and left edges
+        assert_eq!(
+            grid.neighbors4_wrapping(2, 2),
+            [(1, 2), (2, 0), (0, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_neighbors4_wrapping_inside() {
+        // given a grid
+        let grid = Grid::from_input(&Input::from_lines(["abc", "def", "ghi"]));
+
+        // expect cells away from the edges to have the same neighbours as without wrapping
+        assert_eq!(
+            grid.neighbors4_wrapping(1, 1).to_vec(),
+            grid.neighbors4(1, 1)
+        );
+    }
+}
@@ -1,9 +1,56 @@
+mod error;
+mod grid;
+pub mod memoize;
+mod point;
+
+use anyhow::{anyhow, Context};
+use std::fmt::Display;
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
+use std::str::FromStr;
 use std::str::Split;
 
-#[derive(Clone)]
+pub use error::AocError;
+pub use grid::{Adjacency, Grid};
+pub use point::{Direction, Point};
+
+/// The result type used throughout the puzzles
+pub type Result<T> = anyhow::Result<T>;
+
+/// Parsing of a single line of input
+///
+/// Implemented for everything that implements FromStr, days can implement it directly for
+/// types that need richer per-line parsing without colliding with FromStr
+pub trait FromLine: Sized {
+    fn from_line(line: &str) -> Result<Self>;
+}
+
+impl<T> FromLine for T
+where
+    T: FromStr,
+    anyhow::Error: From<T::Err>,
+{
+    fn from_line(line: &str) -> Result<Self> {
+        Ok(line.parse()?)
+    }
+}
+
+/// A day's solution, giving the answers to both parts without knowing the day's functions
+pub trait Solved {
+    /// Solve both parts of the puzzle for the input
+    fn answers(&self, input: &Input) -> Result<(String, String)>;
+
+    /// Assert that solving a sample gives the expected answers to both parts
+    ///
+    /// Panics if the sample can't be solved or any answer differs
+    fn assert_sample(&self, (sample, part1, part2): (&Input, &str, &str)) {
+        let answers = self.answers(sample).expect("Sample can be solved");
+        assert_eq!(answers, (part1.to_string(), part2.to_string()));
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Input(String);
 
 /// Abstraction around the puzzle input, can provide the input as an iterator over lines or as a str
@@ -24,9 +71,28 @@ impl Input {
         )
     }
 
+    /// Create an Input by joining lines with a custom separator, such as `"\r\n"`
+    ///
+    /// The separator is kept as is and not normalized, and unlike [Input::from_lines]
+    /// trailing whitespace is not trimmed
+    pub fn from_lines_sep<I, S>(lines: I, sep: &str) -> Input
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Input(
+            lines
+                .into_iter()
+                .map(|line| line.as_ref().to_string())
+                .collect::<Vec<_>>()
+                .join(sep),
+        )
+    }
+
+    /// Create an Input from a string, converting any `\r\n` or lone `\r` line endings to `\n`
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(input: impl Into<String>) -> Input {
-        Input(input.into())
+        Input(normalize_line_endings(input.into()))
     }
 
     /// Return self without any extra empty newline at the end
@@ -34,26 +100,701 @@ impl Input {
         Input(self.0.trim_end_matches('\n').to_string())
     }
 
+    /// Load the input from a file, converting any `\r\n` or lone `\r` line endings to `\n`
     pub fn load(path: impl AsRef<Path>) -> std::io::Result<Input> {
+        Input::from_reader(File::open(path)?)
+    }
+
+    /// Load the input from a file exactly as it is, without normalizing line endings
+    pub fn load_raw(path: impl AsRef<Path>) -> std::io::Result<Input> {
         let mut input = String::new();
         File::open(path)?.read_to_string(&mut input)?;
         Ok(Input(input))
     }
 
+    /// Read a file line by line, calling `f` for every line without keeping the whole file in memory
+    ///
+    /// Line endings are stripped from each line before it is passed on
+    pub fn load_lines_streaming(
+        path: impl AsRef<Path>,
+        mut f: impl FnMut(&str) -> Result<()>,
+    ) -> Result<()> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            f(line.trim_end_matches(['\n', '\r']))?;
+            line.clear();
+        }
+        Ok(())
+    }
+
+    /// Load the input from the file given as the first command line argument,
+    /// or from stdin if there is no argument
+    pub fn from_arg_or_stdin() -> std::io::Result<Input> {
+        Input::from_arg_or_reader(std::env::args().nth(1), std::io::stdin())
+    }
+
+    fn from_arg_or_reader(arg: Option<String>, reader: impl Read) -> std::io::Result<Input> {
+        match arg {
+            Some(path) => Input::load(path),
+            None => Input::from_reader(reader),
+        }
+    }
+
+    /// Read the input from any reader, normalizing line endings like [Input::load]
+    pub fn from_reader<R: Read>(mut reader: R) -> std::io::Result<Input> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        Ok(Input(normalize_line_endings(input)))
+    }
+
     /// Get the input as a string
     pub fn as_str(&self) -> &str {
         &self.0
     }
 
     /// Get the input as an iterator of lines
-    pub fn as_lines(&self) -> Split<char> {
+    pub fn as_lines(&self) -> Split<'_, char> {
         self.0.split('\n')
     }
+
+    /// Parse every line, stopping at the first line that fails to parse
+    pub fn parse_lines<T: FromLine>(&self) -> Result<Vec<T>> {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .enumerate()
+            .map(|(index, line)| {
+                T::from_line(line).with_context(|| format!("Failed to parse line {}", index + 1))
+            })
+            .collect()
+    }
+
+    /// Parse every line, returning both the successfully parsed lines and
+    /// the index and error of every line that failed to parse
+    pub fn try_parse_lines<T>(&self) -> (Vec<T>, Vec<(usize, String)>)
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut parsed = Vec::new();
+        let mut errors = Vec::new();
+        for (index, line) in self.trim_trailing_newlines().as_lines().enumerate() {
+            match line.parse::<T>() {
+                Ok(value) => parsed.push(value),
+                Err(error) => errors.push((index, error.to_string())),
+            }
+        }
+        (parsed, errors)
+    }
+
+    /// Get all overlapping windows of `size` characters across the whole input,
+    /// optionally leaving out the newlines
+    ///
+    /// Panics if size is 0
+    pub fn char_windows(&self, size: usize, skip_newlines: bool) -> impl Iterator<Item = String> {
+        assert!(size > 0, "Window size must be non-zero");
+        let chars: Vec<char> = self
+            .0
+            .chars()
+            .filter(|c| !skip_newlines || *c != '\n')
+            .collect();
+        let count = (chars.len() + 1).saturating_sub(size);
+        (0..count).map(move |start| chars[start..start + size].iter().collect())
+    }
+
+    /// Remove the longest whitespace prefix common to every non-blank line,
+    /// blank lines become empty
+    ///
+    /// Like Python's `textwrap.dedent`, tabs and spaces are not considered equal
+    pub fn dedent(&self) -> Input {
+        let indentation = self
+            .as_lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .reduce(|common, indentation| {
+                // Shorten the common prefix to where the indentations start to differ
+                let length = common
+                    .char_indices()
+                    .zip(indentation.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(indentation.len()), |((index, _), _)| index);
+                &common[..length]
+            })
+            .unwrap_or("");
+        Input(
+            self.as_lines()
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        ""
+                    } else {
+                        line.strip_prefix(indentation).unwrap_or(line)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /// Add a prefix to every non-blank line
+    pub fn indent(&self, prefix: &str) -> Input {
+        Input(
+            self.as_lines()
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        line.to_string()
+                    } else {
+                        format!("{}{}", prefix, line)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /// Compare with another input line by line, ignoring trailing whitespace on each line
+    /// and any trailing newlines
+    pub fn eq_ignoring_trailing_ws(&self, other: &Input) -> bool {
+        itertools::equal(
+            self.0.trim_end().split('\n').map(str::trim_end),
+            other.0.trim_end().split('\n').map(str::trim_end),
+        )
+    }
+
+    /// Get the input as an iterator of lines from last to first, ignoring trailing newlines
+    pub fn as_lines_rev(&self) -> impl Iterator<Item = &str> {
+        self.0.trim_end_matches('\n').rsplit('\n')
+    }
+
+    /// Get the input as an iterator of lines with leading and trailing whitespace removed
+    ///
+    /// Empty lines are kept as empty strings
+    pub fn trimmed_lines(&self) -> impl Iterator<Item = &str> {
+        self.as_lines().map(str::trim)
+    }
+
+    /// Find the first line that satisfies a predicate, together with its index
+    pub fn find_line(&self, pred: impl Fn(&str) -> bool) -> Option<(usize, &str)> {
+        self.as_lines().enumerate().find(|(_, line)| pred(line))
+    }
+
+    /// Count the lines that satisfy a predicate
+    pub fn count_lines(&self, pred: impl Fn(&str) -> bool) -> usize {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .filter(|line| pred(line))
+            .count()
+    }
+
+    /// Get the length in chars of the longest line
+    pub fn max_line_width(&self) -> usize {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the length in chars of the shortest line
+    pub fn min_line_width(&self) -> usize {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .map(|line| line.chars().count())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Check that all non-empty lines have the same length, returning the number of rows and columns
+    ///
+    /// Fails with an [AocError] pointing at where the first line of another length ends or
+    /// goes past the width of the first line
+    pub fn assert_rectangular(&self) -> Result<(usize, usize)> {
+        let mut lines = self
+            .as_lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty());
+        let cols = match lines.next() {
+            Some((_, line)) => line.chars().count(),
+            None => return Ok((0, 0)),
+        };
+        let mut rows = 1;
+        for (index, line) in lines {
+            let width = line.chars().count();
+            if width != cols {
+                return Err(
+                    AocError::new(format!("{} columns, expected {}", width, cols))
+                        .at(index, width.min(cols))
+                        .into(),
+                );
+            }
+            rows += 1;
+        }
+        Ok((rows, cols))
+    }
+
+    /// Replace characters according to a table of (from, to) pairs in a single pass
+    pub fn translate(&self, table: &[(char, char)]) -> Input {
+        Input(
+            self.0
+                .chars()
+                .map(|c| {
+                    table
+                        .iter()
+                        .find(|(from, _)| *from == c)
+                        .map_or(c, |(_, to)| *to)
+                })
+                .collect(),
+        )
+    }
+
+    /// Get the input as a grid of single digit numbers
+    pub fn digit_grid(&self) -> Result<Vec<Vec<u8>>> {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| {
+                        c.to_digit(10)
+                            .map(|digit| digit as u8)
+                            .ok_or_else(|| anyhow!("Not a digit: {}", c))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Apply a function to every line, collecting the results
+    pub fn map_lines<T>(&self, f: impl Fn(&str) -> T) -> Vec<T> {
+        self.trim_trailing_newlines().as_lines().map(f).collect()
+    }
+
+    /// Apply a function to every line in parallel, collecting the results in line order
+    #[cfg(feature = "parallel")]
+    pub fn par_map_lines<T: Send>(&self, f: impl Fn(&str) -> T + Sync) -> Vec<T> {
+        use rayon::prelude::*;
+
+        let input = self.trim_trailing_newlines();
+        let lines: Vec<&str> = input.as_lines().collect();
+        lines.into_par_iter().map(&f).collect()
+    }
+
+    /// Group indented lines under the closest preceding non-indented header line
+    ///
+    /// Children are returned without their indentation and blank lines are skipped.
+    /// Indented lines before the first header are grouped under an empty header
+    pub fn indented_blocks(&self) -> Vec<(String, Vec<String>)> {
+        let mut blocks: Vec<(String, Vec<String>)> = Vec::new();
+        for line in self.as_lines().filter(|line| !line.trim().is_empty()) {
+            if line.starts_with(char::is_whitespace) {
+                if blocks.is_empty() {
+                    blocks.push((String::new(), Vec::new()));
+                }
+                let (_, children) = blocks.last_mut().expect("Checked above");
+                children.push(line.trim().to_string());
+            } else {
+                blocks.push((line.to_string(), Vec::new()));
+            }
+        }
+        blocks
+    }
+
+    /// Return a copy with only the first `n` lines
+    pub fn take_lines(&self, n: usize) -> Input {
+        Input::from_lines(self.trim_trailing_newlines().as_lines().take(n))
+    }
+
+    /// Return a copy without the first `n` lines
+    pub fn skip_lines(&self, n: usize) -> Input {
+        Input::from_lines(self.trim_trailing_newlines().as_lines().skip(n))
+    }
+
+    /// Render the input with row indices down the left and column indices, modulo 10,
+    /// across the top for debugging grid puzzles
+    pub fn render_with_gutters(&self) -> String {
+        let input = self.trim_trailing_newlines();
+        let gutter = input.as_lines().count().saturating_sub(1).to_string().len();
+        let header = (0..input.max_line_width())
+            .map(|col| char::from_digit((col % 10) as u32, 10).expect("Always a digit"))
+            .collect::<String>();
+        let mut rendered = format!("{:gutter$} {}\n", "", header);
+        for (row, line) in input.as_lines().enumerate() {
+            rendered += &format!("{:>gutter$} {}\n", row, line);
+        }
+        rendered
+    }
+
+    /// Split the lines into `n` contiguous chunks of roughly equal size
+    ///
+    /// The first chunks get one extra line each when the lines can't be divided evenly
+    pub fn split_into(&self, n: usize) -> Vec<Input> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let input = self.trim_trailing_newlines();
+        let lines = input.as_lines().collect::<Vec<_>>();
+        let (size, remainder) = (lines.len() / n, lines.len() % n);
+        let mut start = 0;
+        (0..n)
+            .map(|chunk| {
+                let end = start + size + usize::from(chunk < remainder);
+                let chunk = Input::from_lines(&lines[start..end]);
+                start = end;
+                chunk
+            })
+            .collect()
+    }
+
+    /// Check if the input only contains ASCII characters
+    pub fn is_ascii(&self) -> bool {
+        self.0.is_ascii()
+    }
+
+    /// Return a copy with all ASCII letters in lowercase, leaving other characters as they are
+    pub fn to_ascii_lowercase(&self) -> Input {
+        Input(self.0.to_ascii_lowercase())
+    }
+
+    /// Return a copy with all ASCII letters in uppercase, leaving other characters as they are
+    pub fn to_ascii_uppercase(&self) -> Input {
+        Input(self.0.to_ascii_uppercase())
+    }
+
+    /// Get the length of the input in bytes
+    ///
+    /// Grid coordinates, such as the columns of day 3, currently assume one byte per char,
+    /// so this only equals [Input::char_len] for ASCII input
+    pub fn byte_len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get the length of the input in chars
+    pub fn char_len(&self) -> usize {
+        self.0.chars().count()
+    }
+
+    /// Join all lines into a single string with a separator between each line
+    pub fn joined(&self, sep: &str) -> String {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+
+    /// Return a copy where each run of spaces and tabs within a line is collapsed into a single
+    /// space and the ends of the lines are trimmed
+    pub fn collapse_whitespace(&self) -> Input {
+        Input(
+            self.0
+                .split('\n')
+                .map(|line| {
+                    line.split([' ', '\t'])
+                        .filter(|word| !word.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /// Get the length in chars of each line
+    pub fn line_lengths(&self) -> Vec<usize> {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .map(|line| line.chars().count())
+            .collect()
+    }
+
+    /// Find the index of the first line that makes a predicate fail, by binary searching over
+    /// growing prefixes of the input
+    ///
+    /// The predicate must pass for all prefixes before the bad line and fail for all prefixes
+    /// including it. Returns None if the predicate passes for the whole input
+    pub fn bisect(&self, predicate: impl Fn(&Input) -> bool) -> Option<usize> {
+        let lines = self.trim_trailing_newlines().as_lines().count();
+        // Search for the shortest failing prefix, knowing that the empty prefix passes
+        let (mut passing, mut failing) = (0, lines + 1);
+        while failing - passing > 1 {
+            let middle = (passing + failing) / 2;
+            if predicate(&self.take_lines(middle)) {
+                passing = middle;
+            } else {
+                failing = middle;
+            }
+        }
+        (failing <= lines).then(|| failing - 1)
+    }
+
+    /// Apply a function to every character of the input together with its row and column
+    pub fn map_grid<T>(&self, f: impl Fn(usize, usize, char) -> T) -> Vec<Vec<T>> {
+        self.trim_trailing_newlines()
+            .as_lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| f(row, col, c))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Split the input into blocks separated by blank lines and parse each whole block,
+    /// including its internal newlines
+    ///
+    /// Any run of lines that are empty or only whitespace separates two blocks
+    pub fn blocks_as<T: FromStr>(&self) -> std::result::Result<Vec<T>, T::Err> {
+        let mut blocks = Vec::new();
+        let mut block = Vec::new();
+        for line in self.as_lines() {
+            if !line.trim().is_empty() {
+                block.push(line);
+            } else if !block.is_empty() {
+                blocks.push(block.join("\n"));
+                block.clear();
+            }
+        }
+        if !block.is_empty() {
+            blocks.push(block.join("\n"));
+        }
+        blocks.iter().map(|block| block.parse()).collect()
+    }
+
+    /// Return a copy where whitespace is trimmed from both ends of every line
+    ///
+    /// Unlike [Input::collapse_whitespace], whitespace within the lines is left as it is
+    pub fn trim_each_line(&self) -> Input {
+        Input(
+            self.0
+                .split('\n')
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /// Hash the content of the input with 64 bit FNV-1a, which unlike the std hashers is stable
+    /// across runs and Rust versions, making it usable as a cache key
+    pub fn content_hash(&self) -> u64 {
+        self.0.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    /// Get the input as a grid of single digit numbers
+    ///
+    /// Blank lines are skipped like in [Input::assert_rectangular].
+    /// Fails with an [AocError] pointing at the first cell that isn't a digit
+    pub fn number_grid(&self) -> Result<Grid<u32>> {
+        let input = self.trim_trailing_newlines();
+        let (rows, cols) = input.assert_rectangular()?;
+        let digits = input
+            .as_lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        c.to_digit(10).ok_or_else(|| {
+                            AocError::new(format!("Not a digit: {:?}", c)).at(row, col)
+                        })
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Grid::from_fn(cols, rows, |row, col| digits[row][col]))
+    }
+
+    /// Iterate over the lines that contain a substring, ignoring trailing newlines
+    pub fn lines_containing<'a>(&'a self, needle: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0
+            .trim_end_matches('\n')
+            .split('\n')
+            .filter(move |line| line.contains(needle))
+    }
+
+    /// Iterate over the lines together with the integers on each line, as found by
+    /// [numbers_with_positions], ignoring trailing newlines
+    pub fn enumerate_numbers(&self) -> impl Iterator<Item = Result<(usize, Vec<i64>)>> + '_ {
+        self.0
+            .trim_end_matches('\n')
+            .split('\n')
+            .enumerate()
+            .map(|(index, line)| {
+                let numbers = numbers_with_positions(line)
+                    .with_context(|| format!("Invalid number on line {}", index + 1))?
+                    .into_iter()
+                    .map(|(_start, _end, value)| value)
+                    .collect();
+                Ok((index, numbers))
+            })
+    }
+
+    /// Return a copy where every character that isn't a decimal digit is removed from each line
+    pub fn digits_only(&self) -> Input {
+        Input(
+            self.0
+                .chars()
+                .filter(|c| c.is_ascii_digit() || *c == '\n')
+                .collect(),
+        )
+    }
+
+    /// Fold over the lines with leading and trailing whitespace removed, see [Input::trimmed_lines]
+    pub fn fold_lines<B>(&self, init: B, f: impl FnMut(B, &str) -> B) -> B {
+        self.trim_trailing_newlines().trimmed_lines().fold(init, f)
+    }
+
+    /// Split the input on an arbitrary delimiter, returning each piece as its own Input
+    pub fn split_on(&self, delimiter: &str) -> Vec<Input> {
+        self.0
+            .split(delimiter)
+            .map(|piece| Input(piece.to_string()))
+            .collect()
+    }
+}
+
+/// Assert that the answers to both parts match a file with the expected answers on two lines,
+/// for regression testing against saved solutions
+///
+/// Panics if the file can't be read or an answer differs
+pub fn assert_answers(actual: (impl Display, impl Display), expected_path: impl AsRef<Path>) {
+    let expected_path = expected_path.as_ref();
+    let expected = Input::load(expected_path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", expected_path.display(), e));
+    let mut lines = expected.as_lines().map(str::trim);
+    let expected = (
+        lines.next().unwrap_or_default(),
+        lines.next().unwrap_or_default(),
+    );
+    let actual = (actual.0.to_string(), actual.1.to_string());
+    assert_eq!(
+        (actual.0.as_str(), actual.1.as_str()),
+        expected,
+        "Answers differ from {}",
+        expected_path.display()
+    );
+}
+
+/// Find every integer in a line as (start, end, value), where start and end are the byte range
+/// of the integer, end exclusive
+///
+/// A `-` directly before the digits makes the integer negative. Fails on an integer that
+/// doesn't fit in an i64
+pub fn numbers_with_positions(line: &str) -> Result<Vec<(usize, usize, i64)>> {
+    let bytes = line.as_bytes();
+    let mut numbers = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        let negative = bytes[position] == b'-'
+            && bytes
+                .get(position + 1)
+                .is_some_and(|next| next.is_ascii_digit());
+        if !negative && !bytes[position].is_ascii_digit() {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        position += usize::from(negative);
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            position += 1;
+        }
+        let value = line[start..position].parse().with_context(|| {
+            format!(
+                "Number {} at byte {} doesn't fit in an i64",
+                &line[start..position],
+                start
+            )
+        })?;
+        numbers.push((start, position, value));
+    }
+    Ok(numbers)
+}
+
+/// Embed the puzzle input of a day at compile time so that it doesn't need to be read at runtime
+///
+/// `include_input!("day03")` embeds `day03/input` and `include_input!("day03", "sample")`
+/// embeds `day03/sample`, both relative to the workspace root
+#[macro_export]
+macro_rules! include_input {
+    ($day:literal) => {
+        $crate::include_input!($day, "input")
+    };
+    ($day:literal, $file:literal) => {
+        $crate::Input::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../",
+            $day,
+            "/",
+            $file
+        )))
+    };
+}
+
+/// Expand a comma separated list of numbers and inclusive `a-b` ranges into all the numbers
+///
+/// An inverted range such as `5-3` yields no numbers, anything that isn't a number is an error
+pub fn expand_ranges(spec: &str) -> Result<Vec<i64>> {
+    let mut numbers = Vec::new();
+    for item in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        // Skip the first character when looking for the dash so that negative numbers work
+        match item.char_indices().skip(1).find(|(_, c)| *c == '-') {
+            Some((dash, _)) => {
+                let start: i64 = item[..dash]
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid range {}", item))?;
+                let end: i64 = item[dash + 1..]
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid range {}", item))?;
+                numbers.extend(start..=end);
+            }
+            None => numbers.push(
+                item.parse()
+                    .with_context(|| format!("Invalid number {}", item))?,
+            ),
+        }
+    }
+    Ok(numbers)
+}
+
+/// Pair up the lines of two inputs, stopping at the end of the shorter one
+pub fn zip_lines<'a>(a: &'a Input, b: &'a Input) -> impl Iterator<Item = (&'a str, &'a str)> {
+    a.as_lines().zip(b.as_lines())
+}
+
+/// Run-length encode a slice into pairs of item and number of consecutive repetitions
+pub fn rle<T: PartialEq + Clone>(items: &[T]) -> Vec<(T, usize)> {
+    let mut runs: Vec<(T, usize)> = Vec::new();
+    for item in items {
+        match runs.last_mut() {
+            Some((last, count)) if last == item => *count += 1,
+            _ => runs.push((item.clone(), 1)),
+        }
+    }
+    runs
+}
+
+/// Convert `\r\n` and lone `\r` line endings to `\n`
+fn normalize_line_endings(input: String) -> String {
+    if input.contains('\r') {
+        input.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        input
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Input;
+    use super::{
+        assert_answers, expand_ranges, numbers_with_positions, rle, zip_lines, AocError, FromLine,
+        Input, Result, Solved,
+    };
 
     #[test]
     fn test_as_str() {
@@ -96,4 +837,1039 @@ mod tests {
             vec!["a line", "another line"],
         );
     }
+
+    #[test]
+    fn test_split_on() {
+        // given some input separated by a delimiter
+        let input = Input("a\n===\nb\n===\nc".to_string());
+
+        // when the input is split on the delimiter
+        let pieces = input.split_on("\n===\n");
+
+        // then each piece is its own input
+        itertools::assert_equal(pieces.iter().map(Input::as_str), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_split_on_delimiter_without_newlines() {
+        // given some input with the delimiter on lines of its own
+        let input = Input("a\n===\nb\n===\nc".to_string());
+
+        // when the input is split on just the delimiter
+        let pieces = input.split_on("===");
+
+        // then the newlines around the delimiter are kept in the pieces
+        itertools::assert_equal(
+            pieces.iter().map(Input::as_str),
+            vec!["a\n", "\nb\n", "\nc"],
+        );
+    }
+
+    #[test]
+    fn test_from_arg_or_reader_without_arg_reads_reader() {
+        // given no argument and a reader with some input
+        let reader = "from reader".as_bytes();
+
+        // when the input is read
+        let input = Input::from_arg_or_reader(None, reader).unwrap();
+
+        // then it comes from the reader
+        assert_eq!("from reader", input.as_str());
+    }
+
+    #[test]
+    fn test_from_arg_or_reader_with_arg_loads_file() {
+        // given a file with some input
+        let path = std::env::temp_dir().join("util_test_from_arg_or_reader");
+        std::fs::write(&path, "from file").unwrap();
+
+        // when the input is read with the path as argument
+        let input = Input::from_arg_or_reader(
+            Some(path.to_string_lossy().to_string()),
+            "from reader".as_bytes(),
+        )
+        .unwrap();
+
+        // then it comes from the file
+        assert_eq!("from file", input.as_str());
+    }
+
+    #[test]
+    fn test_try_parse_lines_collects_all_errors() {
+        // given some input with two malformed lines
+        let input = Input::from_lines(["1", "two", "3", "four", "5"]);
+
+        // when the lines are parsed
+        let (parsed, errors) = input.try_parse_lines::<u32>();
+
+        // then the good lines are parsed and both errors are collected
+        assert_eq!(parsed, vec![1, 3, 5]);
+        itertools::assert_equal(errors.iter().map(|(index, _)| *index), vec![1, 3]);
+    }
+
+    #[rstest::rstest]
+    #[case("a line\r\nanother line")]
+    #[case("a line\ranother line")]
+    #[case("a line\r\nanother line\rlast line")]
+    fn test_from_str_normalizes_line_endings(#[case] text: &str) {
+        // when input with non-unix line endings is created
+        let input = Input::from_str(text);
+
+        // expect the lines to be split without any carriage returns left
+        assert!(!input.as_str().contains('\r'));
+        assert_eq!(input.as_lines().next(), Some("a line"));
+        assert_eq!(input.as_lines().nth(1), Some("another line"));
+    }
+
+    #[test]
+    fn test_load_normalizes_and_load_raw_does_not() {
+        // given a file with mixed line endings
+        let path = std::env::temp_dir().join("util_test_load_raw");
+        std::fs::write(&path, "a\r\nb\rc\n").unwrap();
+
+        // expect load to normalize the line endings and load_raw to keep them
+        assert_eq!(Input::load(&path).unwrap().as_str(), "a\nb\nc\n");
+        assert_eq!(Input::load_raw(&path).unwrap().as_str(), "a\r\nb\rc\n");
+    }
+
+    #[test]
+    fn test_char_windows() {
+        // given some input
+        let input = Input("abc".to_string());
+
+        // expect windows of two characters to overlap
+        itertools::assert_equal(input.char_windows(2, false), vec!["ab", "bc"]);
+    }
+
+    #[test]
+    fn test_char_windows_skipping_newlines() {
+        // given some input spanning two lines
+        let input = Input("ab\nc".to_string());
+
+        // expect the windows to include or skip the newline as requested
+        itertools::assert_equal(input.char_windows(2, false), vec!["ab", "b\n", "\nc"]);
+        itertools::assert_equal(input.char_windows(2, true), vec!["ab", "bc"]);
+    }
+
+    #[test]
+    fn test_dedent() {
+        // given some indented input
+        let input = Input("    a line\n      indented line\n\n    last line".to_string());
+
+        // expect dedenting to remove the common indentation
+        assert_eq!(
+            "a line\n  indented line\n\nlast line",
+            input.dedent().as_str()
+        );
+    }
+
+    #[test]
+    fn test_dedent_with_whitespace_only_line() {
+        // given a whitespace-only line indented deeper than the other lines
+        let input = Input::from_str("    a\n      \n    b");
+
+        // expect it to become empty
+        assert_eq!("a\n\nb", input.dedent().as_str());
+    }
+
+    #[test]
+    fn test_dedent_with_mixed_tabs_and_spaces() {
+        // given lines indented with a tab and with spaces
+        let input = Input::from_str("\tfoo\n    bar");
+
+        // expect nothing to be removed since they share no indentation
+        assert_eq!("\tfoo\n    bar", input.dedent().as_str());
+
+        // given lines sharing a tab but with different indentation after it
+        let input = Input::from_str("\t  foo\n\t\tbar");
+
+        // expect only the tab to be removed
+        assert_eq!("  foo\n\tbar", input.dedent().as_str());
+    }
+
+    #[test]
+    fn test_dedent_with_non_ascii_whitespace() {
+        // given lines indented with an ideographic space and with spaces
+        let input = Input::from_str("\u{3000}a\n  b");
+
+        // expect nothing to be removed since they share no indentation
+        assert_eq!("\u{3000}a\n  b", input.dedent().as_str());
+
+        // given lines sharing the ideographic space
+        let input = Input::from_str("\u{3000}a\n\u{3000} b");
+
+        // expect it to be removed
+        assert_eq!("a\n b", input.dedent().as_str());
+    }
+
+    #[test]
+    fn test_indent_is_inverse_of_dedent() {
+        // given some flush-left input
+        let input = Input("a line\n  indented line\n\nlast line".to_string());
+
+        // when it is indented
+        let indented = input.indent("    ");
+
+        // then every non-blank line is prefixed and dedenting gives back the original
+        assert_eq!(
+            "    a line\n      indented line\n\n    last line",
+            indented.as_str()
+        );
+        assert_eq!(input.as_str(), indented.dedent().as_str());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(u32, u32);
+
+    impl FromLine for Pair {
+        fn from_line(line: &str) -> Result<Self> {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| anyhow::anyhow!("Invalid pair {}", line))?;
+            Ok(Pair(a.parse()?, b.parse()?))
+        }
+    }
+
+    #[test]
+    fn test_parse_lines_with_from_line() {
+        // given some input of pairs
+        let input = Input::from_lines(["1-2", "3-4", "5-6"]);
+
+        // when the lines are parsed
+        let pairs: Vec<Pair> = input.parse_lines().unwrap();
+
+        // then each line is parsed with FromLine
+        assert_eq!(pairs, vec![Pair(1, 2), Pair(3, 4), Pair(5, 6)]);
+    }
+
+    #[test]
+    fn test_parse_lines_with_from_str() {
+        // given some input of numbers
+        let input = Input::from_lines(["1", "2", "x"]);
+
+        // when the lines are parsed with a FromStr type
+        let result = input.parse_lines::<u32>();
+
+        // then the bad line is reported
+        assert!(result.unwrap_err().to_string().contains("line 3"));
+    }
+
+    #[test]
+    fn test_eq_ignoring_trailing_ws() {
+        // given two inputs that only differ by trailing whitespace
+        let input = Input("a line  \nanother line\t\n".to_string());
+        let other = Input("a line\nanother line".to_string());
+
+        // expect them to compare equal
+        assert!(input.eq_ignoring_trailing_ws(&other));
+        assert!(other.eq_ignoring_trailing_ws(&input));
+    }
+
+    #[test]
+    fn test_eq_ignoring_trailing_ws_respects_leading_ws() {
+        // given two inputs that differ by leading whitespace
+        let input = Input(" a line".to_string());
+        let other = Input("a line".to_string());
+
+        // expect them to differ
+        assert!(!input.eq_ignoring_trailing_ws(&other));
+    }
+
+    #[test]
+    fn test_trimmed_lines() {
+        // given some input with padded lines
+        let input = Input("  a  \n  b  ".to_string());
+
+        // expect the lines to be trimmed
+        itertools::assert_equal(input.trimmed_lines(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_trimmed_lines_keeps_empty_lines() {
+        // given some input with a blank line
+        let input = Input("a\n   \nb".to_string());
+
+        // expect the blank line to be kept as an empty string
+        itertools::assert_equal(input.trimmed_lines(), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn test_count_lines() {
+        // given the day 3 sample
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]);
+
+        // expect 7 lines to contain a digit
+        assert_eq!(
+            7,
+            input.count_lines(|line| line.chars().any(|c| c.is_ascii_digit()))
+        );
+    }
+
+    #[test]
+    fn test_assert_rectangular() {
+        // given some rectangular input
+        let input = Input("abc\ndef\n".to_string());
+
+        // expect the dimensions to be returned
+        assert_eq!((2, 3), input.assert_rectangular().unwrap());
+    }
+
+    #[test]
+    fn test_assert_rectangular_ragged() {
+        // given some ragged input
+        let input = Input("abc\ndef\ngh\nij".to_string());
+
+        // expect the error to point at the end of the first offending line
+        let error = input.assert_rectangular().unwrap_err();
+        let error = error.downcast::<AocError>().unwrap();
+        assert_eq!(error.location(), Some((2, 2)));
+        assert_eq!(error.to_string(), "line 3, column 3: 2 columns, expected 3");
+    }
+
+    #[test]
+    fn test_translate() {
+        // given some input with both # and * symbols
+        let input = Input::from_lines(["...*..", "..#...", "*..#.."]);
+
+        // when # is translated to *
+        let translated = input.translate(&[('#', '*')]);
+
+        // then all symbols are *
+        assert_eq!(4, translated.as_str().matches('*').count());
+        assert!(!translated.as_str().contains('#'));
+    }
+
+    #[test]
+    fn test_rle() {
+        // given some items with repetitions
+        let items = [1, 1, 2, 3, 3, 3];
+
+        // expect them to be run-length encoded
+        assert_eq!(vec![(1, 2), (2, 1), (3, 3)], rle(&items));
+    }
+
+    #[test]
+    fn test_rle_empty() {
+        // expect encoding nothing to give no runs
+        assert_eq!(Vec::<(char, usize)>::new(), rle::<char>(&[]));
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        // given a grid of digits
+        let input = Input::from_lines(["123", "456"]);
+
+        // expect each digit to become a number
+        assert_eq!(
+            vec![vec![1, 2, 3], vec![4, 5, 6]],
+            input.digit_grid().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_digit_grid_with_letter() {
+        // given a grid with a letter
+        let input = Input::from_lines(["123", "4a6"]);
+
+        // expect an error
+        assert!(input.digit_grid().is_err());
+    }
+
+    #[test]
+    fn test_load_lines_streaming() {
+        // given a file with some lines
+        let path = std::env::temp_dir().join("util_test_load_lines_streaming");
+        std::fs::write(&path, "a\r\nbb\nccc\n").unwrap();
+
+        // when the file is streamed, accumulating the line lengths
+        let mut lengths = Vec::new();
+        Input::load_lines_streaming(&path, |line| {
+            lengths.push(line.len());
+            Ok(())
+        })
+        .unwrap();
+
+        // then every line was seen without its line ending
+        assert_eq!(vec![1, 2, 3], lengths);
+    }
+
+    #[test]
+    fn test_map_lines() {
+        // given some input
+        let input = Input::from_lines(["a", "bb", "ccc"]);
+
+        // expect the function to be applied to each line
+        assert_eq!(vec![1, 2, 3], input.map_lines(str::len));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_map_lines_equals_map_lines() {
+        // given some input with many lines
+        let input = Input::from_lines((0..1000).map(|n| n.to_string()));
+
+        // expect mapping in parallel to give the same result as mapping sequentially
+        let square = |line: &str| line.parse::<u64>().unwrap().pow(2);
+        assert_eq!(input.map_lines(square), input.par_map_lines(square));
+    }
+
+    fn returns_util_result() -> Result<()> {
+        let number: u32 = "42".parse()?;
+        anyhow::ensure!(number == 42, "Unexpected number {}", number);
+        Ok(())
+    }
+
+    #[test]
+    fn test_result_alias_works_with_anyhow() {
+        // expect a function returning util::Result to work with ? and the anyhow macros
+        assert!(returns_util_result().is_ok());
+    }
+
+    #[test]
+    fn test_find_line() {
+        // given the day 4 sample
+        let input = Input::from_lines([
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+        ]);
+
+        // expect the first line containing a colon to be found
+        assert_eq!(
+            Some((0, "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")),
+            input.find_line(|line| line.contains(':'))
+        );
+        // and the first line containing 61 to be the second one
+        assert_eq!(
+            Some(1),
+            input
+                .find_line(|line| line.contains("61"))
+                .map(|(index, _)| index)
+        );
+        // and no line to contain a hash
+        assert_eq!(None, input.find_line(|line| line.contains('#')));
+    }
+
+    #[test]
+    fn test_as_lines_rev() {
+        // given some input with a trailing newline
+        let input = Input("a\nb\nc\n".to_string());
+
+        // expect the lines to be iterated in reverse
+        itertools::assert_equal(input.as_lines_rev(), vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_eq() {
+        // given two inputs built from the same lines
+        let input = Input::from_lines(["a line", "another line"]);
+        let other = Input::from_lines(["a line", "another line"]);
+
+        // expect them to be equal, and different from other content
+        assert_eq!(input, other);
+        assert_ne!(input, Input::from_lines(["a line"]));
+    }
+
+    #[test]
+    fn test_as_hashmap_key() {
+        // given a map keyed by input
+        let mut answers = std::collections::HashMap::new();
+        answers.insert(Input::from_lines(["a line"]), 1);
+
+        // expect an equal input to find the value
+        assert_eq!(Some(&1), answers.get(&Input::from_str("a line")));
+    }
+
+    #[test]
+    fn test_debug_prints_content() {
+        // expect the debug output to include the content
+        assert!(format!("{:?}", Input::from_str("a line")).contains("a line"));
+    }
+
+    #[test]
+    fn test_indented_blocks() {
+        // given two headers with two indented children each
+        let input = Input::from_lines(["first:", "  a", "  b", "second:", "\tc", "\td"]);
+
+        // expect the children to be grouped under their headers
+        assert_eq!(
+            vec![
+                ("first:".to_string(), vec!["a".to_string(), "b".to_string()]),
+                (
+                    "second:".to_string(),
+                    vec!["c".to_string(), "d".to_string()]
+                ),
+            ],
+            input.indented_blocks()
+        );
+    }
+
+    #[test]
+    fn test_line_widths() {
+        // given some ragged input
+        let input = Input("abc\na\nabcde\n".to_string());
+
+        // expect the longest and shortest widths
+        assert_eq!(5, input.max_line_width());
+        assert_eq!(1, input.min_line_width());
+    }
+
+    #[test]
+    fn test_line_widths_count_chars() {
+        // given a line with multi-byte characters
+        let input = Input("åäö\nab".to_string());
+
+        // expect the widths to be counted in chars, not bytes
+        assert_eq!(3, input.max_line_width());
+        assert_eq!(2, input.min_line_width());
+    }
+
+    #[test]
+    fn test_take_and_skip_lines() {
+        // given some input with five lines
+        let input = Input::from_lines(["1", "2", "3", "4", "5"]);
+
+        // expect taking and skipping to split the lines
+        assert_eq!(Input::from_lines(["1", "2"]), input.take_lines(2));
+        assert_eq!(Input::from_lines(["3", "4", "5"]), input.skip_lines(2));
+        // and going past the end to take everything or nothing
+        assert_eq!(input, input.take_lines(10));
+        assert_eq!("", input.skip_lines(10).as_str());
+    }
+
+    #[test]
+    fn test_zip_lines() {
+        // given a two line and a three line input
+        let a = Input::from_lines(["a", "b"]);
+        let b = Input::from_lines(["1", "2", "3"]);
+
+        // expect the lines to be paired up until the shorter input ends
+        itertools::assert_equal(zip_lines(&a, &b), vec![("a", "1"), ("b", "2")]);
+    }
+
+    #[test]
+    fn test_from_lines_sep_with_custom_separator() {
+        // when Input is created from lines separated by a non-whitespace separator
+        let input = Input::from_lines_sep(["a", "b"], ";");
+
+        // then the separator is only put between the lines
+        assert_eq!("a;b", input.as_str());
+    }
+
+    #[test]
+    fn test_from_lines_sep() {
+        // given some lines
+        let input = ["a line", "another line"];
+
+        // when Input is created from the lines with CRLF line endings
+        let input = Input::from_lines_sep(input, "\r\n");
+
+        // then as_str returns the lines joined by CRLF
+        assert_eq!("a line\r\nanother line", input.as_str());
+    }
+
+    #[test]
+    fn test_render_with_gutters() {
+        // given a grid with more than ten columns
+        let input = Input::from_lines(["467..114...", "...*......."]);
+
+        // when it is rendered with gutters
+        let rendered = input.render_with_gutters();
+
+        // then the first row is the column index header and each line is prefixed by its row
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "  01234567890");
+        assert_eq!(lines[1], "0 467..114...");
+        assert_eq!(lines[2], "1 ...*.......");
+    }
+
+    #[test]
+    fn test_split_into() {
+        // given ten lines
+        let input = Input::from_lines((0..10).map(|line| line.to_string()));
+
+        // when they are split into three chunks
+        let chunks = input.split_into(3);
+
+        // then the first chunk gets the extra line and no lines are lost
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.as_lines().count())
+                .collect::<Vec<_>>(),
+            vec![4, 3, 3]
+        );
+        assert_eq!(chunks[0], Input::from_lines(["0", "1", "2", "3"]));
+        assert_eq!(chunks[2], Input::from_lines(["7", "8", "9"]));
+    }
+
+    struct LineCounter;
+
+    impl Solved for LineCounter {
+        fn answers(&self, input: &Input) -> Result<(String, String)> {
+            let lines = input.as_lines().count();
+            Ok((lines.to_string(), (lines * 2).to_string()))
+        }
+    }
+
+    #[test]
+    fn test_solved_assert_sample() {
+        // given a solution and a sample
+        let sample = Input::from_lines(["a", "b", "c"]);
+
+        // expect the sample to be solved with the expected answers
+        LineCounter.assert_sample((&sample, "3", "6"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_solved_assert_sample_with_wrong_answer() {
+        // given a solution and a sample
+        let sample = Input::from_lines(["a", "b", "c"]);
+
+        // expect a wrong answer to fail the assertion
+        LineCounter.assert_sample((&sample, "3", "7"));
+    }
+
+    #[test]
+    fn test_is_ascii() {
+        // expect plain input to be ASCII but input with full-width digits not to be
+        assert!(Input::from_lines(["two1nine", "abc"]).is_ascii());
+        assert!(!Input::from_lines(["two１nine", "abc"]).is_ascii());
+    }
+
+    #[test]
+    fn test_ascii_case() {
+        // given input with mixed case and non-ASCII characters
+        let input = Input::from_lines(["TwO1nine", "ÅäÖ"]);
+
+        // expect only the ASCII letters to change case
+        assert_eq!(
+            input.to_ascii_lowercase(),
+            Input::from_lines(["two1nine", "ÅäÖ"])
+        );
+        assert_eq!(
+            input.to_ascii_uppercase(),
+            Input::from_lines(["TWO1NINE", "ÅäÖ"])
+        );
+    }
+
+    #[test]
+    fn test_byte_and_char_len() {
+        // given ASCII input
+        let ascii = Input::from_lines(["ab", "c"]);
+
+        // expect the byte and char lengths to be equal
+        assert_eq!((ascii.byte_len(), ascii.char_len()), (4, 4));
+
+        // given input with a multi-byte character
+        let unicode = Input::from_lines(["aå", "c"]);
+
+        // expect the byte length to be longer than the char length
+        assert_eq!((unicode.byte_len(), unicode.char_len()), (5, 4));
+    }
+
+    #[test]
+    fn test_joined() {
+        // expect the lines to be joined by the separator
+        assert_eq!(Input::from_lines(["a", "b"]).joined(" "), "a b");
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        // given a line with runs of spaces and tabs
+        let input = Input::from_str("Card 1:  41 48 |  6\t\t31 \nCard  2: 1 | 2\n");
+
+        // expect each run to become a single space and lines to keep their newlines
+        assert_eq!(
+            input.collapse_whitespace().as_str(),
+            "Card 1: 41 48 | 6 31\nCard 2: 1 | 2\n"
+        );
+    }
+
+    #[test]
+    fn test_assert_answers() {
+        // given a file with two expected answers
+        let path = std::env::temp_dir().join("util_test_assert_answers");
+        std::fs::write(&path, "4361\n467835\n").unwrap();
+
+        // expect matching answers of any displayable types to pass
+        assert_answers((4361, "467835"), &path);
+    }
+
+    #[test]
+    #[should_panic(expected = "Answers differ")]
+    fn test_assert_answers_with_wrong_answer() {
+        // given a file with two expected answers
+        let path = std::env::temp_dir().join("util_test_assert_answers_with_wrong_answer");
+        std::fs::write(&path, "4361\n467835\n").unwrap();
+
+        // expect a wrong second answer to fail the assertion
+        assert_answers((4361, 467836), &path);
+    }
+
+    #[test]
+    fn test_line_lengths() {
+        // given a ragged input
+        let input = Input::from_str("abc\nde\n\nfghi\n");
+
+        // expect the length of every line, including the empty one
+        assert_eq!(input.line_lengths(), vec![3, 2, 0, 4]);
+    }
+
+    #[test]
+    fn test_numbers_with_positions() {
+        // expect both numbers to be found with their byte ranges
+        assert_eq!(
+            numbers_with_positions("12 and 34").unwrap(),
+            vec![(0, 2, 12), (7, 9, 34)]
+        );
+    }
+
+    #[test]
+    fn test_numbers_with_positions_negative_and_unicode() {
+        // expect a minus before digits to be part of the number, but not a lone minus,
+        // and byte positions to count multi-byte characters by their bytes
+        assert_eq!(
+            numbers_with_positions("å-5 - 7-").unwrap(),
+            vec![(2, 4, -5), (7, 8, 7)]
+        );
+    }
+
+    #[test]
+    fn test_numbers_with_positions_overflow() {
+        // given a line with a number that doesn't fit in an i64
+        let error = numbers_with_positions("id 99999999999999999999").unwrap_err();
+
+        // expect the error to point at the number
+        assert_eq!(
+            error.to_string(),
+            "Number 99999999999999999999 at byte 3 doesn't fit in an i64"
+        );
+    }
+
+    #[test]
+    fn test_include_input() {
+        // given a fixture embedded at compile time
+        let input = crate::include_input!("util", "fixtures/embedded");
+
+        // expect it to be the contents of the file
+        assert_eq!(input, Input::from_str("embedded\nat compile time\n"));
+    }
+
+    #[test]
+    fn test_bisect() {
+        // given some numbers where the line at index 6 can't be parsed
+        let input = Input::from_lines(["1", "2", "3", "4", "5", "6", "x", "8", "9", "10"]);
+
+        // when bisecting for the first line that fails to parse
+        let bad_line = input.bisect(|prefix| prefix.parse_lines::<u32>().is_ok());
+
+        // then the bad line is found
+        assert_eq!(bad_line, Some(6));
+    }
+
+    #[test]
+    fn test_bisect_without_bad_line() {
+        // given input where every line can be parsed
+        let input = Input::from_lines(["1", "2", "3"]);
+
+        // expect there to be no bad line
+        assert_eq!(
+            input.bisect(|prefix| prefix.parse_lines::<u32>().is_ok()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_map_grid() {
+        // given a grid
+        let input = Input::from_lines(["abc", "def"]);
+
+        // when each cell is mapped to the parity of its position
+        let parities = input.map_grid(|row, col, _| (row + col) % 2);
+
+        // then the parities form a checkerboard
+        assert_eq!(parities, vec![vec![0, 1, 0], vec![1, 0, 1]]);
+    }
+
+    #[test]
+    fn test_from_reader() {
+        // given a reader with mixed line endings
+        let reader = std::io::Cursor::new("a\r\nb\n".as_bytes());
+
+        // when the input is read from it
+        let input = Input::from_reader(reader).unwrap();
+
+        // then the line endings are normalized
+        assert_eq!(input, Input::from_str("a\nb\n"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Record {
+        name: String,
+        values: Vec<u32>,
+    }
+
+    impl std::str::FromStr for Record {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self> {
+            let mut lines = s.lines();
+            let name = lines.next().unwrap_or_default().to_string();
+            let values = lines
+                .map(str::parse)
+                .collect::<std::result::Result<_, _>>()?;
+            Ok(Record { name, values })
+        }
+    }
+
+    #[test]
+    fn test_blocks_as() {
+        // given two multi-line records separated by a blank line
+        let input = Input::from_lines(["first", "1", "2", "", "second", "3"]);
+
+        // when the blocks are parsed
+        let records = input.blocks_as::<Record>().unwrap();
+
+        // then each record is parsed from all of its lines
+        assert_eq!(
+            records,
+            vec![
+                Record {
+                    name: "first".to_string(),
+                    values: vec![1, 2]
+                },
+                Record {
+                    name: "second".to_string(),
+                    values: vec![3]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_as_with_several_blank_lines() {
+        // given records separated by more than one blank line, one of them only whitespace
+        let input = Input::from_str("first\n1\n\n\nsecond\n2\n  \n\t\nthird\n3\n");
+
+        // when the blocks are parsed
+        let records = input.blocks_as::<Record>().unwrap();
+
+        // then every run of blank lines separates two records
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["first", "second", "third"]
+        );
+    }
+
+    #[test]
+    fn test_blocks_as_fails_on_bad_block() {
+        // given a record with a value that isn't a number
+        let input = Input::from_lines(["first", "1", "", "second", "x"]);
+
+        // expect parsing the blocks to fail
+        assert!(input.blocks_as::<Record>().is_err());
+    }
+
+    #[test]
+    fn test_trim_each_line() {
+        // given a block of space padded lines
+        let input = Input::from_str("  Card 1:  41 | 83  \n\tCard 2: 13 | 61\n");
+
+        // expect only the ends of each line to be trimmed
+        assert_eq!(
+            input.trim_each_line().as_str(),
+            "Card 1:  41 | 83\nCard 2: 13 | 61\n"
+        );
+    }
+
+    #[test]
+    fn test_content_hash() {
+        // given two identical inputs and a different one
+        let input = Input::from_lines(["467..114..", "...*......"]);
+        let same = Input::from_str("467..114..\n...*......");
+        let different = Input::from_lines(["467..114..", "...#......"]);
+
+        // expect identical inputs to hash equally and different inputs to differ
+        assert_eq!(input.content_hash(), same.content_hash());
+        assert_ne!(input.content_hash(), different.content_hash());
+        // and the hash to be the standard FNV-1a hash
+        assert_eq!(Input::from_str("a").content_hash(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_expand_ranges() {
+        // expect single numbers to be kept and ranges to be expanded inclusively
+        assert_eq!(expand_ranges("1,3-5,8").unwrap(), vec![1, 3, 4, 5, 8]);
+        assert_eq!(expand_ranges("-2--1, 0").unwrap(), vec![-2, -1, 0]);
+    }
+
+    #[test]
+    fn test_expand_ranges_inverted_and_invalid() {
+        // expect an inverted range to yield nothing
+        assert_eq!(expand_ranges("5-3").unwrap(), Vec::<i64>::new());
+        // and anything that isn't a number to be an error
+        assert!(expand_ranges("1,x-3").is_err());
+        assert!(expand_ranges("1,a").is_err());
+    }
+
+    #[test]
+    fn test_number_grid() {
+        // given a grid of digits
+        let input = Input::from_lines(["123", "456"]);
+
+        // when it is parsed as a number grid
+        let grid = input.number_grid().unwrap();
+
+        // then each cell is the number of the digit
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 2), Some(&6));
+    }
+
+    #[test]
+    fn test_number_grid_with_blank_line() {
+        // given a grid of digits with a blank line between the rows
+        let input = Input::from_lines(["12", "", "34"]);
+
+        // when it is parsed as a number grid
+        let grid = input.number_grid().unwrap();
+
+        // then the blank line is skipped
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 0), Some(&3));
+    }
+
+    #[test]
+    fn test_number_grid_with_stray_letter_after_blank_line() {
+        // given a grid with a blank line before a stray letter
+        let input = Input::from_lines(["12", "", "3x"]);
+
+        // expect the error to point at the line of the letter in the input
+        let error = input.number_grid().unwrap_err();
+        let error = error.downcast::<AocError>().unwrap();
+        assert_eq!(error.location(), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_number_grid_with_stray_letter() {
+        // given a grid of digits with a stray letter
+        let input = Input::from_lines(["123", "45x"]);
+
+        // when it is parsed as a number grid
+        let error = input.number_grid().unwrap_err();
+
+        // then the error points at the letter, displayed as the third column of the second line
+        let error = error.downcast::<AocError>().unwrap();
+        assert_eq!(error.location(), Some((1, 2)));
+        assert_eq!(error.to_string(), "line 2, column 3: Not a digit: 'x'");
+    }
+
+    #[test]
+    fn test_lines_containing() {
+        // given the day 3 sample
+        let input = Input::from_lines([
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]);
+
+        // expect only the lines with a * to be yielded
+        assert_eq!(
+            input.lines_containing("*").collect::<Vec<_>>(),
+            vec!["...*......", "617*......", "...$.*...."]
+        );
+    }
+
+    #[test]
+    fn test_lines_containing_with_trailing_newline() {
+        // given a single line ending with a newline
+        let input = Input::from_str("a\n");
+
+        // expect an empty needle to match only that line
+        assert_eq!(input.lines_containing("").collect::<Vec<_>>(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_enumerate_numbers() {
+        // given two lines with numbers
+        let input = Input::from_lines(["Card 1: 41 48 | 83", "x=-3, y=7"]);
+
+        // expect the numbers of each line together with the line index
+        assert_eq!(
+            input
+                .enumerate_numbers()
+                .collect::<Result<Vec<_>>>()
+                .unwrap(),
+            vec![(0, vec![1, 41, 48, 83]), (1, vec![-3, 7])]
+        );
+    }
+
+    #[test]
+    fn test_enumerate_numbers_with_trailing_newline() {
+        // given some numbers ending with a newline
+        let input = Input::from_str("1 2\n3\n");
+
+        // expect no numbers for a line after the last newline
+        assert_eq!(
+            input
+                .enumerate_numbers()
+                .collect::<Result<Vec<_>>>()
+                .unwrap(),
+            vec![(0, vec![1, 2]), (1, vec![3])]
+        );
+    }
+
+    #[test]
+    fn test_enumerate_numbers_overflow() {
+        // given a number on the second line that doesn't fit in an i64
+        let input = Input::from_lines(["1", "99999999999999999999"]);
+
+        // expect the first line to be fine and the second to fail naming its line
+        let mut numbers = input.enumerate_numbers();
+        assert_eq!(numbers.next().unwrap().unwrap(), (0, vec![1]));
+        let error = numbers.next().unwrap().unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_digits_only() {
+        // expect everything but the digits and newlines to be removed
+        assert_eq!(Input::from_str("a1b2").digits_only().as_str(), "12");
+        assert_eq!(
+            Input::from_lines(["pqr3stu8vwx", "treb7uchet", "abc"])
+                .digits_only()
+                .as_str(),
+            "38\n7\n"
+        );
+    }
+
+    #[test]
+    fn test_fold_lines() {
+        // given some padded lines
+        let input = Input::from_str("  abc\nde  \nf\n");
+
+        // expect folding over the trimmed lines to sum their lengths
+        assert_eq!(input.fold_lines(0, |total, line| total + line.len()), 6);
+    }
 }
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Remembers computed values by key, for dynamic programming puzzles
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Get the value for a key, computing it with `f` only if it hasn't been computed before
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&K) -> V) -> &V {
+        self.values.entry(key).or_insert_with_key(f)
+    }
+
+    /// Get the number of remembered values
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<K: Eq + Hash, V> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Memo;
+
+    #[test]
+    fn test_get_or_compute_computes_once_per_key() {
+        // given an empty memo
+        let mut memo = Memo::new();
+        let mut computed = Vec::new();
+
+        // when values are requested for some keys, some of them repeatedly
+        for key in [1, 2, 1, 3, 2, 1] {
+            let value = *memo.get_or_compute(key, |key| {
+                computed.push(*key);
+                key * 10
+            });
+            assert_eq!(value, key * 10);
+        }
+
+        // then each distinct key was only computed once
+        assert_eq!(computed, vec![1, 2, 3]);
+        assert_eq!(memo.len(), 3);
+    }
+}
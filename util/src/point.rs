@@ -0,0 +1,135 @@
+/// A position on a grid, rows grow southwards and columns grow eastwards
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub fn new(row: i64, col: i64) -> Point {
+        Point { row, col }
+    }
+
+    /// Get the chessboard distance to another point, where diagonal neighbours are 1 apart
+    pub fn chebyshev(&self, other: &Point) -> i64 {
+        (self.row - other.row)
+            .abs()
+            .max((self.col - other.col).abs())
+    }
+
+    /// Get the point one step away in a direction
+    pub fn step(&self, direction: Direction) -> Point {
+        let (row, col) = direction.delta();
+        Point::new(self.row + row, self.col + col)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    /// Get the change in (row, column) from moving one step in this direction
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::South => (1, 0),
+            Direction::East => (0, 1),
+            Direction::West => (0, -1),
+        }
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Point};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Direction::North, (-1, 0))]
+    #[case(Direction::South, (1, 0))]
+    #[case(Direction::East, (0, 1))]
+    #[case(Direction::West, (0, -1))]
+    fn test_delta(#[case] direction: Direction, #[case] expected: (i64, i64)) {
+        // expect the delta to move one step in the direction
+        assert_eq!(expected, direction.delta());
+    }
+
+    #[rstest]
+    #[case(Direction::North)]
+    #[case(Direction::South)]
+    #[case(Direction::East)]
+    #[case(Direction::West)]
+    fn test_turning(#[case] direction: Direction) {
+        // expect turning four times in the same direction to return to the start
+        assert_eq!(
+            direction,
+            direction
+                .turn_right()
+                .turn_right()
+                .turn_right()
+                .turn_right()
+        );
+        assert_eq!(
+            direction,
+            direction.turn_left().turn_left().turn_left().turn_left()
+        );
+        // and turning twice to face the opposite direction
+        assert_eq!(direction.opposite(), direction.turn_right().turn_right());
+        assert_eq!(direction.opposite(), direction.turn_left().turn_left());
+    }
+
+    #[rstest]
+    #[case(Point::new(3, 5), 0)]
+    #[case(Point::new(2, 4), 1)]
+    #[case(Point::new(4, 5), 1)]
+    #[case(Point::new(1, 6), 2)]
+    #[case(Point::new(3, -1), 6)]
+    fn test_chebyshev(#[case] other: Point, #[case] expected: i64) {
+        // expect the distance to be the largest of the row and column distances
+        assert_eq!(expected, Point::new(3, 5).chebyshev(&other));
+    }
+
+    #[test]
+    fn test_step() {
+        // given a point
+        let point = Point::new(3, 5);
+
+        // expect stepping to move one cell in the direction
+        assert_eq!(Point::new(2, 5), point.step(Direction::North));
+        assert_eq!(Point::new(3, 6), point.step(Direction::East));
+        assert_eq!(Point::new(4, 5), point.step(Direction::South));
+        assert_eq!(Point::new(3, 4), point.step(Direction::West));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+
+use util::Result;
+
+fn main() -> Result<()> {
+    let day = std::env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("Usage: xtask DAY"))?
+        .parse()
+        .context("DAY must be a number")?;
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask is inside the workspace");
+    let created = scaffold(workspace, day)?;
+    println!("Created {}", created.display());
+    Ok(())
+}
+
+/// Create a new day crate from a copy of the workspace's template directory and register it
+/// in the workspace
+///
+/// Returns the directory of the new crate
+fn scaffold(workspace: &Path, day: u32) -> Result<PathBuf> {
+    let name = format!("day{:02}", day);
+    let target = workspace.join(&name);
+    if target.exists() {
+        bail!("{} already exists", target.display());
+    }
+
+    let manifest_path = workspace.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    // Add the new day last in the list of members
+    let members_end = manifest
+        .find("members = [")
+        .and_then(|start| manifest[start..].find(']').map(|end| start + end))
+        .ok_or_else(|| anyhow!("No workspace members in {}", manifest_path.display()))?;
+    let manifest = format!(
+        "{}  \"{}\",\n{}",
+        &manifest[..members_end],
+        name,
+        &manifest[members_end..]
+    );
+
+    copy_dir(&workspace.join("template"), &target)?;
+    for file in ["Cargo.toml", "src/main.rs"] {
+        let path = target.join(file);
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        fs::write(&path, contents.replace("dayXX", &name))?;
+    }
+    fs::write(&manifest_path, manifest)?;
+    Ok(target)
+}
+
+/// Recursively copy a directory, including hidden files such as the IDE settings
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))? {
+        let entry = entry?;
+        let destination = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{copy_dir, scaffold};
+    use std::fs;
+    use std::path::Path;
+    use util::Result;
+
+    #[test]
+    pub fn test_scaffold() -> Result<()> {
+        // Given a workspace with a single day and the template
+        let workspace = std::env::temp_dir().join("xtask_test_scaffold");
+        let _ = fs::remove_dir_all(&workspace);
+        copy_dir(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("../template"),
+            &workspace.join("template"),
+        )?;
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n  \"day01\",\n]\n",
+        )?;
+
+        // When day 5 is scaffolded
+        let created = scaffold(&workspace, 5)?;
+
+        // Then the crate is created from the template and added to the workspace
+        assert_eq!(created, workspace.join("day05"));
+        assert!(fs::read_to_string(created.join("Cargo.toml"))?.contains("name = \"day05\""));
+        assert!(fs::read_to_string(created.join("src/main.rs"))?.contains("fn part1("));
+        assert!(created.join(".idea/modules.xml").exists());
+        assert_eq!(
+            fs::read_to_string(workspace.join("Cargo.toml"))?,
+            "[workspace]\nmembers = [\n  \"day01\",\n  \"day05\",\n]\n"
+        );
+
+        // And scaffolding the same day again fails
+        assert!(scaffold(&workspace, 5).is_err());
+        Ok(())
+    }
+}